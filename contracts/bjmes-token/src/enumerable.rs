@@ -4,7 +4,13 @@ use cw20::{
     SpenderAllowanceInfo,
 };
 
-use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES};
+use crate::msg::{
+    DelegatorsResponse, HolderCountResponse, HolderResponse, TopHoldersResponse,
+    TransferWhitelistResponse,
+};
+use crate::state::{
+    ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, DELEGATORS, HOLDERS, HOLDER_COUNT, TRANSFER_WHITELIST,
+};
 use cw_storage_plus::Bound;
 
 // settings for pagination
@@ -78,16 +84,95 @@ pub fn query_all_accounts(
     Ok(AllAccountsResponse { accounts })
 }
 
+pub fn query_delegators(
+    deps: Deps,
+    delegate: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DelegatorsResponse> {
+    let delegate_addr = deps.api.addr_validate(&delegate)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+
+    let delegators = DELEGATORS
+        .prefix(&delegate_addr)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Into::into))
+        .collect::<StdResult<_>>()?;
+
+    Ok(DelegatorsResponse { delegators })
+}
+
+pub fn query_transfer_whitelist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TransferWhitelistResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+
+    let contracts = TRANSFER_WHITELIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Into::into))
+        .collect::<StdResult<_>>()?;
+
+    Ok(TransferWhitelistResponse { contracts })
+}
+
+pub fn query_top_holders(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TopHoldersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    // continue below the current balance of the last holder of the previous page
+    let end = match start_after {
+        Some(address) => {
+            let balance = BALANCES
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default();
+            Some((balance.u128(), address))
+        }
+        None => None,
+    };
+    let end = end
+        .as_ref()
+        .map(|(balance, address)| Bound::exclusive((*balance, address)));
+
+    let holders = HOLDERS
+        .keys(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(balance, address)| HolderResponse {
+                address: address.into(),
+                balance: balance.into(),
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(TopHoldersResponse { holders })
+}
+
+pub fn query_holder_count(deps: Deps) -> StdResult<HolderCountResponse> {
+    let count = HOLDER_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    Ok(HolderCountResponse { count })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, DepsMut, Uint128};
-    use cw20::{Cw20Coin, TokenInfoResponse};
+    use cosmwasm_std::{coins, Addr, DepsMut, Uint128};
+    use cw20::{Cw20Coin, MinterResponse, TokenInfoResponse};
 
-    use crate::contract::{execute, instantiate, query_token_info};
-    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::contract::{execute, instantiate, migrate, query_token_info};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
 
     // this will set up the instantiation for other tests
     fn do_instantiate(mut deps: DepsMut, addr: &str, amount: Uint128) -> TokenInfoResponse {
@@ -99,8 +184,15 @@ mod tests {
                 address: addr.into(),
                 amount,
             }],
-            mint: None,
+            mint: Some(MinterResponse {
+                minter: addr.into(),
+                cap: None,
+            }),
             marketing: None,
+            bonding: None,
+            max_lock_duration: None,
+            admin: None,
+            emission: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -317,4 +409,112 @@ mod tests {
                 .unwrap();
         assert_eq!(accounts.accounts, expected_order[3..].to_vec());
     }
+
+    #[test]
+    fn query_top_holders_works() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let acct1 = String::from("acct01");
+        let acct2 = String::from("zebra");
+        let acct3 = String::from("nice");
+
+        do_instantiate(deps.as_mut(), &acct1, Uint128::new(300));
+
+        let info = mock_info(acct1.as_ref(), &[]);
+        let env = mock_env();
+        for (recipient, amount) in [(&acct2, 500u128), (&acct3, 100), (&acct3, 300)] {
+            let msg = ExecuteMsg::Mint {
+                recipient: recipient.clone(),
+                amount: Uint128::new(amount),
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let holder = |address: &str, balance: u128| HolderResponse {
+            address: address.to_string(),
+            balance: Uint128::new(balance),
+        };
+        let expected = vec![
+            holder(&acct2, 500),
+            holder(&acct3, 400),
+            holder(&acct1, 300),
+        ];
+
+        let holders = query_top_holders(deps.as_ref(), None, None).unwrap();
+        assert_eq!(holders.holders, expected);
+        assert_eq!(query_holder_count(deps.as_ref()).unwrap().count, 3);
+
+        // let's do pagination
+        let holders = query_top_holders(deps.as_ref(), None, Some(1)).unwrap();
+        assert_eq!(holders.holders, expected[0..1].to_vec());
+        let holders = query_top_holders(deps.as_ref(), Some(acct2), Some(10)).unwrap();
+        assert_eq!(holders.holders, expected[1..].to_vec());
+
+        // burning everything drops the holder
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(300),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+        };
+        let info = mock_info(acct3.as_ref(), &[]);
+        execute(deps.as_mut(), env, info, msg).unwrap();
+
+        let holders = query_top_holders(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            holders.holders,
+            vec![holder("zebra", 500), holder("nice", 300)]
+        );
+        assert_eq!(query_holder_count(deps.as_ref()).unwrap().count, 2);
+    }
+
+    #[test]
+    fn migrate_backfills_holder_index() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let acct1 = String::from("acct01");
+        let acct2 = String::from("zebra");
+
+        do_instantiate(deps.as_mut(), &acct1, Uint128::new(300));
+        let info = mock_info(acct1.as_ref(), &[]);
+        let msg = ExecuteMsg::Mint {
+            recipient: acct2.clone(),
+            amount: Uint128::new(500),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // roll the storage back to a release without the holder index
+        for (address, balance) in [(&acct1, 300u128), (&acct2, 500)] {
+            HOLDERS.remove(&mut deps.storage, (balance, &Addr::unchecked(address)));
+        }
+        HOLDER_COUNT.remove(&mut deps.storage);
+        cw2::set_contract_version(&mut deps.storage, "bjmes-token", "0.14.0").unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+
+        let holders = query_top_holders(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            holders.holders,
+            vec![
+                HolderResponse {
+                    address: acct2,
+                    balance: Uint128::new(500),
+                },
+                HolderResponse {
+                    address: acct1.clone(),
+                    balance: Uint128::new(300),
+                },
+            ]
+        );
+        assert_eq!(query_holder_count(deps.as_ref()).unwrap().count, 2);
+
+        // burning everything drops the backfilled holder
+        let info = mock_info(acct1.as_ref(), &[]);
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(300),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(query_holder_count(deps.as_ref()).unwrap().count, 1);
+    }
 }
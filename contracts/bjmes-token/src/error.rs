@@ -1,4 +1,6 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_controllers::{AdminError, HookError};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -38,4 +40,61 @@ pub enum ContractError {
 
     #[error("Duplicate initial balance addresses")]
     DuplicateInitialBalanceAddresses {},
+
+    #[error("Bonding is disabled")]
+    BondingDisabled {},
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("No claims that can be released yet")]
+    NothingToClaim {},
+
+    #[error("Unknown reply id {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Only {bonded} bonded tokens can be unbonded")]
+    UnbondExceedsBonded { bonded: Uint128 },
+
+    #[error("Locking is disabled")]
+    LockingDisabled {},
+
+    #[error("Locks cannot exceed {max} blocks")]
+    LockTooLong { max: u64 },
+
+    #[error("Locks can be extended but never shortened")]
+    LockCannotBeShortened {},
+
+    #[error("No active lock")]
+    NoActiveLock {},
+
+    #[error("Cannot use locked tokens")]
+    TokensLocked {},
+
+    #[error("No delegate to undelegate from")]
+    NotDelegated {},
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    #[error("bJMES can only be transferred from or to a whitelisted contract")]
+    NotWhitelisted {},
+
+    #[error("Emission is disabled")]
+    EmissionDisabled {},
+
+    #[error("No emission epoch is due")]
+    NothingToEmit {},
+
+    #[error("Minter cannot exceed its cap")]
+    MinterCapExceeded {},
+
+    #[error("Minter not registered")]
+    MinterNotFound {},
+
+    #[error("Pruning needs a governance contract")]
+    PruningDisabled {},
 }
@@ -0,0 +1,60 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_binary, Binary, CosmosMsg, StdResult, Uint128, WasmMsg};
+
+/// BalanceDiff shows the old and new bJMES balance of an address
+#[cw_serde]
+pub struct BalanceDiff {
+    pub address: String,
+    pub old: Uint128,
+    pub new: Uint128,
+}
+
+impl BalanceDiff {
+    pub fn new<T: Into<String>>(address: T, old: Uint128, new: Uint128) -> Self {
+        BalanceDiff {
+            address: address.into(),
+            old,
+            new,
+        }
+    }
+}
+
+/// BalanceChangedHookMsg should be de/serialized under `BalanceChangedHook()` variant in a ExecuteMsg.
+/// This contains a list of all diffs on the given transaction.
+#[cw_serde]
+pub struct BalanceChangedHookMsg {
+    pub diffs: Vec<BalanceDiff>,
+}
+
+impl BalanceChangedHookMsg {
+    pub fn one(diff: BalanceDiff) -> Self {
+        BalanceChangedHookMsg { diffs: vec![diff] }
+    }
+
+    pub fn new(diffs: Vec<BalanceDiff>) -> Self {
+        BalanceChangedHookMsg { diffs }
+    }
+
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = BalanceChangedExecuteMsg::BalanceChangedHook(self);
+        to_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+enum BalanceChangedExecuteMsg {
+    BalanceChangedHook(BalanceChangedHookMsg),
+}
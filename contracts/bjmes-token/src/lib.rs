@@ -2,6 +2,7 @@ pub mod allowances;
 pub mod contract;
 pub mod enumerable;
 mod error;
+pub mod hook;
 pub mod msg;
 #[cfg(any(test, feature = "tests"))]
 pub mod multitest;
@@ -1,10 +1,88 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use cosmwasm_std::{Binary, StdError, StdResult, Uint128};
 use cw20::{Cw20Coin, Logo, MinterResponse};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-pub use cw20::Cw20ExecuteMsg as ExecuteMsg;
+use crate::state::{BondingConfig, EmissionEpoch, Lock};
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Transfer is a base message to move tokens to another account without triggering actions.
+    /// Either the sender or the recipient must be on the transfer whitelist.
+    Transfer { recipient: String, amount: Uint128 },
+    /// Burn is a base message to destroy tokens forever
+    Burn { amount: Uint128 },
+    /// Send is a base message to transfer tokens to a contract and trigger an action
+    /// on the receiving contract. Either the sender or the contract must be on the
+    /// transfer whitelist.
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    /// Only with the "mintable" extension. If authorized, creates amount new tokens
+    /// and adds to the recipient balance.
+    Mint { recipient: String, amount: Uint128 },
+    /// Only with the "marketing" extension. If authorized, updates marketing metadata.
+    /// Setting None/null for any of these will leave it unchanged.
+    /// Setting Some("") will clear this field on the contract storage
+    UpdateMarketing {
+        /// A URL pointing to the project behind this token.
+        project: Option<String>,
+        /// A longer description of the token and it's utility. Designed for tooltips or such
+        description: Option<String>,
+        /// The address (if any) who can update this data structure
+        marketing: Option<String>,
+    },
+    /// If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
+    UploadLogo(Logo),
+    /// Mints bJMES 1:1 for the native JMES sent along
+    Bond {},
+    /// Burns bJMES and creates a claim on the same amount of native JMES,
+    /// released after the unbonding period
+    Unbond { amount: Uint128 },
+    /// Pays out all matured claims of the sender
+    Claim {},
+    /// Locks `amount` more bJMES for `duration` blocks in exchange for extra voting power.
+    /// Adding to an active lock can't end it any earlier.
+    Lock { amount: Uint128, duration: u64 },
+    /// Moves the end of the active lock to `duration` blocks from now, which must be later
+    ExtendLock { duration: u64 },
+    /// Delegates the voting power of the sender's whole balance to `to`, replacing any
+    /// previous delegate
+    Delegate { to: String },
+    /// Takes back the voting power delegated by the sender
+    Undelegate {},
+    /// Only the governance contract. Adds and removes contracts allowed to send or receive bJMES.
+    UpdateTransferWhitelist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Only the admin. Hands over the admin role, or removes it if None.
+    UpdateAdmin { admin: Option<String> },
+    /// Only the admin. Sets the governance contract managing the transfer whitelist and consulted
+    /// when pruning, or removes it if None.
+    UpdateGovernance { governance: Option<String> },
+    /// Only the admin. Adds a contract to be called with a `BalanceChangedHookMsg` on every
+    /// balance change.
+    AddHook { addr: String },
+    /// Only the admin. Removes a balance change hook.
+    RemoveHook { addr: String },
+    /// Only the admin. Registers a minter that can mint up to `cap`, or updates its cap.
+    AddMinter {
+        minter: String,
+        cap: Option<Uint128>,
+    },
+    /// Only the admin. Removes a registered minter.
+    RemoveMinter { minter: String },
+    /// Mints all epochs of the emission schedule that started since the last emission.
+    /// Anyone can call it.
+    Emit {},
+    /// Removes up to `limit` balance snapshots older than the oldest proposal still open on the
+    /// governance contract. Anyone can call it.
+    Prune { limit: Option<u32> },
+}
 
 #[cw_serde]
 pub struct InstantiateMarketingInfo {
@@ -23,6 +101,14 @@ pub struct InstantiateMsg {
     pub initial_balances: Vec<Cw20Coin>,
     pub mint: Option<MinterResponse>,
     pub marketing: Option<InstantiateMarketingInfo>,
+    /// Allows holders to bond native JMES, bonding is disabled if not set
+    pub bonding: Option<BondingConfig>,
+    /// Longest lock in blocks, locking is disabled if not set
+    pub max_lock_duration: Option<u64>,
+    /// Manages the transfer whitelist, hooks and minters, meant to be handed over to governance
+    pub admin: Option<String>,
+    /// Epochs minted by `Emit`, ordered by start time, emission is disabled if not set
+    pub emission: Option<Vec<EmissionEpoch>>,
 }
 
 impl InstantiateMsg {
@@ -77,11 +163,20 @@ pub enum QueryMsg {
     #[returns(cw20::BalanceResponse)]
     Balance { address: String },
     /// BalanceAt returns balance of the given address at the given block, 0 if unset.
+    /// Fails for blocks whose snapshots were pruned.
     #[returns(cw20::BalanceResponse)]
     BalanceAt { address: String, block: u64 },
     /// TotalSupplyAt returns the total token supply at the given block.
     #[returns(Uint128)]
     TotalSupplyAt { block: u64 },
+    /// BalanceAtTime returns balance of the given address at the given timestamp (in seconds),
+    /// including all blocks up to and at that time, 0 if unset. Fails if that time was pruned.
+    #[returns(cw20::BalanceResponse)]
+    BalanceAtTime { address: String, timestamp: u64 },
+    /// TotalSupplyAtTime returns the total token supply at the given timestamp (in seconds).
+    /// Fails if that time was pruned.
+    #[returns(Uint128)]
+    TotalSupplyAtTime { timestamp: u64 },
     /// Returns metadata on the contract - name, decimals, supply, etc.
     #[returns(cw20::TokenInfoResponse)]
     TokenInfo {},
@@ -126,10 +221,139 @@ pub enum QueryMsg {
     /// contract.
     #[returns(cw20::DownloadLogoResponse)]
     DownloadLogo {},
+    /// Returns the pending unbonding claims of the given address
+    #[returns(cw_controllers::ClaimsResponse)]
+    Claims { address: String },
+    /// Returns the bond denom and unbonding period, null if bonding is disabled
+    #[returns(Option<BondingConfig>)]
+    Bonding {},
+    /// Returns the current lock of the given address, null if never locked
+    #[returns(Option<Lock>)]
+    Lock { address: String },
+    /// VotingPowerAt returns the balance of the given address at the given block unless it was
+    /// delegated, plus the decaying bonus of its locked tokens and the power delegated to it.
+    #[returns(VotingPowerResponse)]
+    VotingPowerAt { address: String, block: u64 },
+    /// DelegatedPowerAt returns the sum of the balances delegated to the given address
+    /// at the given block, 0 if unset.
+    #[returns(DelegatedPowerResponse)]
+    DelegatedPowerAt { address: String, block: u64 },
+    /// Returns all accounts delegating to the given delegate. Supports pagination.
+    #[returns(DelegatorsResponse)]
+    Delegators {
+        delegate: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the admin managing hooks and minters
+    #[returns(cw_controllers::AdminResponse)]
+    Admin {},
+    /// Returns the governance contract managing the transfer whitelist, null if not set
+    #[returns(Option<String>)]
+    Governance {},
+    /// Returns all registered minters with their caps and minted amounts
+    #[returns(MintersResponse)]
+    Minters {},
+    /// Returns all contracts called on balance changes
+    #[returns(cw_controllers::HooksResponse)]
+    Hooks {},
+    /// Returns the emission schedule and how much of it was emitted or is due
+    #[returns(EmissionResponse)]
+    Emission {},
+    /// Returns the addresses with the largest balances, largest first. Supports pagination.
+    #[returns(TopHoldersResponse)]
+    TopHolders {
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
+    /// Returns the number of addresses with a non-zero balance
+    #[returns(HolderCountResponse)]
+    HolderCount {},
+    /// Returns all contracts on the transfer whitelist. Supports pagination.
+    #[returns(TransferWhitelistResponse)]
+    TransferWhitelist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct VotingPowerResponse {
+    pub voting_power: Uint128,
 }
 
-#[derive(Serialize, Deserialize, JsonSchema)]
-pub struct MigrateMsg {}
+#[cw_serde]
+pub struct DelegatedPowerResponse {
+    pub delegated_power: Uint128,
+}
+
+#[cw_serde]
+pub struct DelegatorsResponse {
+    pub delegators: Vec<String>,
+}
+
+#[cw_serde]
+pub struct MinterInfoResponse {
+    pub minter: String,
+    pub cap: Option<Uint128>,
+    pub minted: Uint128,
+}
+
+#[cw_serde]
+pub struct MintersResponse {
+    pub minters: Vec<MinterInfoResponse>,
+}
+
+// Subset of the governance contract's queries used to look up the oldest open proposal
+#[cw_serde]
+pub enum GovernanceQueryMsg {
+    OldestOpenProposal {},
+}
+
+#[cw_serde]
+pub struct OldestOpenProposalResponse {
+    pub id: Option<u64>,
+    pub start_block: Option<u64>,
+}
+
+#[cw_serde]
+pub struct EmissionResponse {
+    pub schedule: Vec<EmissionEpoch>,
+    pub emitted_epochs: u32,
+    pub emitted_amount: Uint128,
+    /// Amount `Emit` would mint now
+    pub due_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct TransferWhitelistResponse {
+    pub contracts: Vec<String>,
+}
+
+#[cw_serde]
+pub struct HolderResponse {
+    pub address: String,
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct TopHoldersResponse {
+    pub holders: Vec<HolderResponse>,
+}
+
+#[cw_serde]
+pub struct HolderCountResponse {
+    pub count: u64,
+}
+
+/// Settings added after the first release, each one is only changed if set
+#[derive(Serialize, Deserialize, JsonSchema, Default)]
+pub struct MigrateMsg {
+    pub admin: Option<String>,
+    pub governance: Option<String>,
+    pub bonding: Option<BondingConfig>,
+    pub max_lock_duration: Option<u64>,
+}
 
 #[cfg(test)]
 mod tests {
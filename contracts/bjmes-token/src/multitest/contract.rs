@@ -1,9 +1,14 @@
-use cosmwasm_std::{Addr, StdResult, Uint128};
+use cosmwasm_std::{coins, Addr, StdResult, Uint128};
 use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{
+    DelegatedPowerResponse, ExecuteMsg, InstantiateMsg, QueryMsg, TransferWhitelistResponse,
+    VotingPowerResponse,
+};
+use crate::state::BondingConfig;
 use crate::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg};
+use cw_controllers::ClaimsResponse;
 
 #[derive(Debug, Clone)]
 pub struct BjmesTokenContract(Addr);
@@ -14,12 +19,19 @@ impl BjmesTokenContract {
     }
 
     pub fn store_code(app: &mut App) -> u64 {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
         app.store_code(Box::new(contract))
     }
 
     #[track_caller]
-    pub fn instantiate(app: &mut App, code_id: u64, sender: &Addr, label: &str) -> StdResult<Self> {
+    pub fn instantiate(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        bonding: Option<BondingConfig>,
+        max_lock_duration: Option<u64>,
+    ) -> StdResult<Self> {
         app.instantiate_contract(
             code_id,
             sender.clone(),
@@ -30,6 +42,10 @@ impl BjmesTokenContract {
                 initial_balances: vec![],
                 marketing: None,
                 mint: None,
+                bonding,
+                max_lock_duration,
+                admin: Some(sender.to_string()),
+                emission: None,
             },
             &[],
             label,
@@ -57,6 +73,256 @@ impl BjmesTokenContract {
         .map_err(|err| err.downcast().unwrap())
         // .map(|_| ())
     }
+
+    #[track_caller]
+    pub fn add_minter(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        minter: &Addr,
+        cap: Option<Uint128>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::AddMinter {
+                minter: minter.to_string(),
+                cap,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn prune(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        limit: Option<u32>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Prune { limit },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn bond(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        amount: u128,
+        denom: &str,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Bond {},
+            &coins(amount, denom),
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn unbond(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        amount: Uint128,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Unbond { amount },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn claim(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        app.execute_contract(sender.clone(), self.0.clone(), &ExecuteMsg::Claim {}, &[])
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn transfer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        recipient: &Addr,
+        amount: Uint128,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn update_admin(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        admin: Option<&Addr>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateAdmin {
+                admin: admin.map(Addr::to_string),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn update_governance(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        governance: Option<&Addr>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateGovernance {
+                governance: governance.map(Addr::to_string),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn query_transfer_whitelist(&self, app: &mut App) -> StdResult<Vec<String>> {
+        app.wrap()
+            .query_wasm_smart(
+                self.0.clone(),
+                &QueryMsg::TransferWhitelist {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .map(|res: TransferWhitelistResponse| res.contracts)
+    }
+
+    #[track_caller]
+    pub fn lock(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        amount: Uint128,
+        duration: u64,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Lock { amount, duration },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn extend_lock(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        duration: u64,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::ExtendLock { duration },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn delegate(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        to: &Addr,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Delegate { to: to.to_string() },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn undelegate(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Undelegate {},
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn query_delegated_power_at(
+        &self,
+        app: &mut App,
+        address: &Addr,
+        block: u64,
+    ) -> StdResult<Uint128> {
+        app.wrap()
+            .query_wasm_smart(
+                self.0.clone(),
+                &QueryMsg::DelegatedPowerAt {
+                    address: address.to_string(),
+                    block,
+                },
+            )
+            .map(|res: DelegatedPowerResponse| res.delegated_power)
+    }
+
+    #[track_caller]
+    pub fn query_voting_power_at(
+        &self,
+        app: &mut App,
+        address: &Addr,
+        block: u64,
+    ) -> StdResult<Uint128> {
+        app.wrap()
+            .query_wasm_smart(
+                self.0.clone(),
+                &QueryMsg::VotingPowerAt {
+                    address: address.to_string(),
+                    block,
+                },
+            )
+            .map(|res: VotingPowerResponse| res.voting_power)
+    }
+
+    #[track_caller]
+    pub fn query_claims(&self, app: &mut App, address: &Addr) -> StdResult<ClaimsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Claims {
+                address: address.to_string(),
+            },
+        )
+    }
 }
 
 impl From<BjmesTokenContract> for Addr {
@@ -1,7 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Env, Order, StdResult, Storage, Uint128};
-use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
-use cw_storage_plus::{Bound,  Item, Map, SnapshotMap, Strategy};
+use cosmwasm_std::{Addr, Empty, Env, Order, StdError, StdResult, Storage, Uint128};
+use cw20::{AllowanceResponse, Cw20Coin, Logo, MarketingInfoResponse};
+use cw_controllers::{Admin, Claims, Hooks};
+use cw_storage_plus::{Bound, Item, Map, SnapshotMap, Strategy};
+use cw_utils::Duration;
 
 #[cw_serde]
 pub struct TokenInfo {
@@ -19,13 +21,135 @@ pub struct MinterData {
     pub cap: Option<Uint128>,
 }
 
+#[cw_serde]
+pub struct MinterInfo {
+    /// cap is how many tokens this minter can issue in total
+    pub cap: Option<Uint128>,
+    pub minted: Uint128,
+    /// removed minters keep what they minted in case they are added again
+    #[serde(default)]
+    pub removed: bool,
+}
+
 impl TokenInfo {
     pub fn get_cap(&self) -> Option<Uint128> {
         self.mint.as_ref().and_then(|v| v.cap)
     }
 }
 
+#[cw_serde]
+pub struct BondingConfig {
+    /// Native denom bonded 1:1 into bJMES
+    pub denom: String,
+    /// Time or blocks until unbonded tokens can be claimed
+    pub unbonding_period: Duration,
+}
+
+#[cw_serde]
+pub struct EmissionEpoch {
+    /// Block time in seconds from which the epoch can be emitted
+    pub start: u64,
+    /// Amounts minted to each recipient when the epoch is emitted
+    pub recipients: Vec<Cw20Coin>,
+}
+
+impl EmissionEpoch {
+    pub fn amount(&self) -> Uint128 {
+        self.recipients.iter().map(|coin| coin.amount).sum()
+    }
+}
+
+#[cw_serde]
+pub struct Lock {
+    pub amount: Uint128,
+    /// Block height at which the tokens are unlocked again
+    pub end: u64,
+}
+
+impl Lock {
+    /// Amount that is still locked at `block`
+    pub fn locked_at(&self, block: u64) -> Uint128 {
+        if block < self.end {
+            self.amount
+        } else {
+            Uint128::zero()
+        }
+    }
+
+    /// Extra voting power of the lock at `block`. A lock of `max_lock_duration` blocks doubles the
+    /// voting power of the locked tokens, decaying linearly to nothing at the end of the lock.
+    pub fn voting_power_bonus_at(&self, block: u64, max_lock_duration: u64) -> Uint128 {
+        if block >= self.end {
+            return Uint128::zero();
+        }
+        let remaining = (self.end - block).min(max_lock_duration);
+        self.amount.multiply_ratio(remaining, max_lock_duration)
+    }
+}
+
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+/// ## Description
+/// Minters registered by the admin, next to the minter in [`TokenInfo`].
+pub const MINTERS: Map<&Addr, MinterInfo> = Map::new("minters");
+pub const BONDING: Item<BondingConfig> = Item::new("bonding");
+/// ## Description
+/// Unbonded tokens waiting for the unbonding period to pass.
+pub const CLAIMS: Claims = Claims::new("claims");
+/// ## Description
+/// Native tokens bonded per address, only these can be unbonded again.
+pub const BONDED: Map<&Addr, Uint128> = Map::new("bonded");
+/// ## Description
+/// Supply backed by bonded native tokens, which doesn't count against the cap.
+pub const TOTAL_BONDED: Item<Uint128> = Item::new("total_bonded");
+/// ## Description
+/// Longest lock in blocks, locking is disabled if not set.
+pub const MAX_LOCK_DURATION: Item<u64> = Item::new("max_lock_duration");
+/// ## Description
+/// Contains snapshotted locks at every block, used to compute past voting power.
+pub const LOCKS: SnapshotMap<&Addr, Lock> = SnapshotMap::new(
+    "lock",
+    "lock__checkpoints",
+    "lock__changelog",
+    Strategy::EveryBlock,
+);
+/// ## Description
+/// Contains the snapshotted delegate each delegator has handed its voting power to.
+pub const DELEGATES: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "delegates",
+    "delegates__checkpoints",
+    "delegates__changelog",
+    Strategy::EveryBlock,
+);
+/// ## Description
+/// Delegators per delegate, used to list them.
+pub const DELEGATORS: Map<(&Addr, &Addr), Empty> = Map::new("delegators");
+/// ## Description
+/// Contains the snapshotted sum of the balances delegated to each delegate at every block.
+pub const DELEGATED_POWER: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "delegated_power",
+    "delegated_power__checkpoints",
+    "delegated_power__changelog",
+    Strategy::EveryBlock,
+);
+/// ## Description
+/// Manages hooks and minters, meant to be handed over to governance.
+pub const ADMIN: Admin = Admin::new("admin");
+/// ## Description
+/// Governance contract managing the transfer whitelist, whose open proposals still look up past
+/// balances. Pruning is disabled if not set.
+pub const GOVERNANCE: Item<Addr> = Item::new("governance");
+/// ## Description
+/// Contracts that may send or receive bJMES, holders can't transfer among each other.
+pub const TRANSFER_WHITELIST: Map<&Addr, Empty> = Map::new("transfer_whitelist");
+/// ## Description
+/// Contracts notified with a `BalanceChangedHookMsg` whenever balances change.
+pub const HOOKS: Hooks = Hooks::new("balance_hooks");
+/// ## Description
+/// Epochs minted by `Emit`, ordered by start time.
+pub const EMISSION_SCHEDULE: Item<Vec<EmissionEpoch>> = Item::new("emission_schedule");
+/// ## Description
+/// Number of epochs of the emission schedule that were already minted.
+pub const EMITTED_EPOCHS: Item<u32> = Item::new("emitted_epochs");
 pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
 pub const LOGO: Item<Logo> = Item::new("logo");
 /// ## Description
@@ -36,6 +160,12 @@ pub const BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
     "balance__changelog",
     Strategy::EveryBlock,
 );
+/// ## Description
+/// Index of all addresses with a non-zero balance, ordered by balance.
+pub const HOLDERS: Map<(u128, &Addr), Empty> = Map::new("holders");
+/// ## Description
+/// Number of addresses with a non-zero balance.
+pub const HOLDER_COUNT: Item<u64> = Item::new("holder_count");
 pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
 // TODO: After https://github.com/CosmWasm/cw-plus/issues/670 is implemented, replace this with a `MultiIndex` over `ALLOWANCES`
 pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), AllowanceResponse> =
@@ -45,6 +175,18 @@ pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), AllowanceResponse> =
 /// Contains the history of the xASTRO total supply.
 pub const TOTAL_SUPPLY_HISTORY: Map<u64, Uint128> = Map::new("total_supply_history");
 
+/// ## Description
+/// Contains the last block height with balance changes for every block time in seconds.
+pub const BLOCK_HEIGHTS: Map<u64, u64> = Map::new("block_heights");
+
+/// ## Description
+/// Balance snapshots of blocks before this height were pruned.
+pub const PRUNED_BEFORE: Item<u64> = Item::new("pruned_before");
+
+/// ## Description
+/// The last balance changelog entry visited by `Prune`, the next call continues after it.
+pub const PRUNE_CURSOR: Item<(Addr, u64)> = Item::new("prune_cursor");
+
 /// ## Description
 /// Snapshots the total token supply at current block.
 /// ## Params
@@ -58,9 +200,161 @@ pub fn capture_total_supply_history(
     env: &Env,
     total_supply: Uint128,
 ) -> StdResult<()> {
+    capture_block_time(storage, env)?;
     TOTAL_SUPPLY_HISTORY.save(storage, env.block.height, &total_supply)
 }
 
+/// ## Description
+/// Indexes the current block height by its time, to look up snapshots by timestamp.
+/// Must be called on every balance change.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **env** is an object of type [`Env`].
+pub fn capture_block_time(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    BLOCK_HEIGHTS.save(storage, env.block.time.seconds(), &env.block.height)
+}
+
+/// ## Description
+/// Returns the height of the last block with balance changes at or before `timestamp` (in seconds),
+/// or `None` if there were no changes yet by then.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **timestamp** is an object of type [`u64`].
+pub fn get_height_at_time(storage: &dyn Storage, timestamp: u64) -> StdResult<Option<u64>> {
+    let end = Bound::inclusive(timestamp);
+    BLOCK_HEIGHTS
+        .range(storage, None, Some(end), Order::Descending)
+        .next()
+        .map(|item| item.map(|(_, height)| height))
+        .transpose()
+}
+
+/// ## Description
+/// Moves `address` in the holder index from its `old` to its `new` balance.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **address** is an object of type [`Addr`].
+///
+/// * **old** is an object of type [`Uint128`]. The balance before the change.
+///
+/// * **new** is an object of type [`Uint128`]. The balance after the change.
+pub fn update_holder_index(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    old: Uint128,
+    new: Uint128,
+) -> StdResult<()> {
+    if !old.is_zero() {
+        HOLDERS.remove(storage, (old.u128(), address));
+    }
+    if !new.is_zero() {
+        HOLDERS.save(storage, (new.u128(), address), &Empty {})?;
+    }
+
+    if old.is_zero() != new.is_zero() {
+        let count = HOLDER_COUNT.may_load(storage)?.unwrap_or_default();
+        let count = if new.is_zero() {
+            count
+                .checked_sub(1)
+                .ok_or_else(|| StdError::generic_err("Holder count underflow"))?
+        } else {
+            count + 1
+        };
+        HOLDER_COUNT.save(storage, &count)?;
+    }
+    Ok(())
+}
+
+/// ## Description
+/// Returns a [`cosmwasm_std::StdError`] if the balance snapshots of the given block were pruned.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **block** is an object of type [`u64`].
+pub fn assert_not_pruned(storage: &dyn Storage, block: u64) -> StdResult<()> {
+    match PRUNED_BEFORE.may_load(storage)? {
+        Some(pruned_before) if block < pruned_before => Err(StdError::generic_err(format!(
+            "Balance history before block {} has been pruned",
+            pruned_before
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// ## Description
+/// Removes balance changelog entries of blocks before `retain_from`, visiting at most `limit`
+/// entries after the stored cursor. Returns the number of removed entries.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **retain_from** is an object of type [`u64`]. Snapshots of this block and later are kept.
+///
+/// * **limit** is an object of type [`usize`].
+pub fn prune_balance_history(
+    storage: &mut dyn Storage,
+    retain_from: u64,
+    limit: usize,
+) -> StdResult<u32> {
+    let cursor = PRUNE_CURSOR.may_load(storage)?;
+    let start = cursor
+        .as_ref()
+        .map(|(address, height)| Bound::exclusive((address, *height)));
+    let keys = BALANCES
+        .changelog()
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Start over once the end of the changelog is reached
+    match keys.last() {
+        Some(last) if keys.len() == limit => PRUNE_CURSOR.save(storage, last)?,
+        _ => PRUNE_CURSOR.remove(storage),
+    }
+
+    let mut pruned = 0;
+    for (address, height) in keys {
+        if height < retain_from {
+            BALANCES.changelog().remove(storage, (&address, height));
+            pruned += 1;
+        }
+    }
+
+    let pruned_before = PRUNED_BEFORE.may_load(storage)?.unwrap_or_default();
+    PRUNED_BEFORE.save(storage, &pruned_before.max(retain_from))?;
+
+    Ok(pruned)
+}
+
+/// ## Description
+/// Removes up to `limit` of the oldest block times whose balance snapshots are before
+/// `retain_from`. Returns the number of removed entries.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **retain_from** is an object of type [`u64`]. Snapshots of this block and later are kept.
+///
+/// * **limit** is an object of type [`usize`].
+pub fn prune_block_heights(
+    storage: &mut dyn Storage,
+    retain_from: u64,
+    limit: usize,
+) -> StdResult<u32> {
+    // a block time looks up the snapshots taken after that block
+    let entries = BLOCK_HEIGHTS
+        .range(storage, None, None, Order::Ascending)
+        .take(limit)
+        .take_while(|item| !matches!(item, Ok((_, height)) if height + 1 >= retain_from))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (timestamp, _) in entries.iter() {
+        BLOCK_HEIGHTS.remove(storage, *timestamp);
+    }
+    Ok(entries.len() as u32)
+}
+
 /// ## Description
 /// Returns a [`cosmwasm_std::StdError`] on failure, otherwise returns the total token supply at the given block.
 /// ## Params
@@ -4,7 +4,8 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use distribution::msg::{
-    ConfigResponse, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, QueryMsg,
+    ConfigResponse, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, MigrateMsg,
+    QueryMsg, TreasuryResponse,
 };
 
 fn main() {
@@ -16,8 +17,10 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(GrantResponse), &out_dir);
     export_schema(&schema_for!(GrantsResponse), &out_dir);
+    export_schema(&schema_for!(TreasuryResponse), &out_dir);
 }
@@ -1,16 +1,21 @@
 use std::env;
-use std::ops::{Mul, Sub};
+use std::ops::Sub;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ConfigResponse, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, QueryMsg,
+    ConfigResponse, ExecuteMsg, GovernanceCoreSlotsResponse, GovernanceQueryMsg, GrantResponse,
+    GrantsResponse, InstantiateMsg, MigrateMsg, QueryMsg, TreasuryAsset, TreasuryResponse,
+};
+use crate::state::{
+    grants, legacy_grants, Approver, Asset, Commitment, Config, CoreSlot, Grant, GrantStatus,
+    MilestoneProgress, Recipient, VestingSchedule, COMMITMENTS, CONFIG, MAX_SPLIT_RECIPIENTS,
+    MAX_SPLIT_WEIGHT,
 };
-use crate::state::{grants, Config, Grant, CONFIG};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
-    Response, StdResult, Timestamp, Uint128,
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Storage, Timestamp, Uint128,
 };
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
@@ -50,9 +55,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Grant { grant_id } => to_binary(&query_grant(deps, env, grant_id)?),
         QueryMsg::Grants {
             dao,
+            status,
             start_after,
             limit,
-        } => to_binary(&query_grants(deps, dao, start_after, limit)?),
+        } => to_binary(&query_grants(deps, env, dao, status, start_after, limit)?),
+        QueryMsg::Treasury {} => to_binary(&query_treasury(deps, env)?),
     }
 }
 
@@ -65,37 +72,42 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     })
 }
 
-fn query_grant(deps: Deps, env: Env, grant_id: u64) -> StdResult<Option<GrantResponse>> {
-    let maybe_grant = grants().may_load(deps.storage, grant_id.to_string())?;
-    if maybe_grant.is_none() {
-        return Ok(None);
-    }
-
-    let grant = maybe_grant.unwrap();
-
-    let claimable_amount = claimable_amount(env.block.time, &grant);
-
-    let grant_response = GrantResponse {
+fn grant_response(grant: Grant, env: &Env) -> GrantResponse {
+    GrantResponse {
+        claimable_amount: claimable_amount(env.block.time, &grant),
+        status: grant.status(env.block.time),
         grant_id: grant.grant_id,
         dao: grant.dao,
         amount_approved: grant.amount_approved,
         amount_remaining: grant.amount_remaining,
         started: grant.started,
         expires: grant.expires,
-        claimable_amount,
-    };
+        revoked: grant.revoked,
+        vesting: grant.vesting,
+        asset: grant.asset,
+        recipients: grant.recipients,
+        milestones: grant.milestones,
+        paused_at: grant.paused_at,
+        paused_duration: grant.paused_duration,
+    }
+}
+
+fn query_grant(deps: Deps, env: Env, grant_id: u64) -> StdResult<Option<GrantResponse>> {
+    let maybe_grant = grants().may_load(deps.storage, grant_id)?;
 
-    Ok(Some(grant_response))
+    Ok(maybe_grant.map(|grant| grant_response(grant, &env)))
 }
 
 fn query_grants(
     deps: Deps,
+    env: Env,
     dao: Option<Addr>,
-    start_after: Option<String>,
+    status: Option<GrantStatus>,
+    start_after: Option<u64>,
     limit: Option<u32>,
 ) -> StdResult<GrantsResponse> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let start = start_after.map(Bound::exclusive);
 
     // Select index range for dao query parameter
     let range = match dao {
@@ -109,14 +121,76 @@ fn query_grants(
         }
     };
 
-    let grants: StdResult<Vec<Grant>> = range
+    let grants = range
+        .filter(|item| match (item, status) {
+            (Ok((_, grant)), Some(status)) => grant.status(env.block.time) == status,
+            _ => true,
+        })
         .take(limit)
-        .map(|item| item.map(|(_, grant)| grant))
-        .collect();
+        .map(|item| item.map(|(_, grant)| grant_response(grant, &env)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GrantsResponse { grants })
+}
 
-    let res = GrantsResponse { grants: grants? };
+fn query_treasury(deps: Deps, env: Env) -> StdResult<TreasuryResponse> {
+    let mut commitments = COMMITMENTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, commitment)| commitment))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Always report the native uluna balance
+    let uluna = Asset::default();
+    if !commitments.iter().any(|c| c.asset == uluna) {
+        commitments.insert(
+            0,
+            Commitment {
+                asset: uluna,
+                amount: Uint128::zero(),
+            },
+        );
+    }
+
+    let assets = commitments
+        .into_iter()
+        .map(|commitment| {
+            let balance = commitment
+                .asset
+                .query_balance(&deps.querier, &env.contract.address)?;
+            Ok(TreasuryAsset {
+                asset: commitment.asset,
+                balance,
+                committed: commitment.amount,
+                free: balance.saturating_sub(commitment.amount),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TreasuryResponse { assets })
+}
 
-    Ok(res)
+// Amount of the asset granted but not yet paid out
+fn committed_amount(storage: &dyn Storage, asset: &Asset) -> StdResult<Uint128> {
+    Ok(COMMITMENTS
+        .may_load(storage, asset.to_string())?
+        .map(|commitment| commitment.amount)
+        .unwrap_or_default())
+}
+
+fn update_commitment(
+    storage: &mut dyn Storage,
+    asset: &Asset,
+    update: impl FnOnce(Uint128) -> StdResult<Uint128>,
+) -> StdResult<()> {
+    let amount = update(committed_amount(storage, asset)?)?;
+    COMMITMENTS.save(
+        storage,
+        asset.to_string(),
+        &Commitment {
+            asset: asset.clone(),
+            amount,
+        },
+    )
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -131,20 +205,67 @@ pub fn execute(
             dao,
             duration,
             amount,
-        } => execute_add_grant(deps, env, info, dao, duration, amount),
-        // ExecuteMsg::RevokeGrant { dao } => execute_revoke_grant(deps, env, info, dao),
+            vesting,
+            asset,
+            start,
+        } => execute_add_grant(
+            deps,
+            env,
+            info,
+            GrantParams {
+                dao,
+                duration,
+                amount,
+                vesting,
+                asset,
+                start,
+            },
+        ),
+        ExecuteMsg::RevokeGrant { grant_id } => execute_revoke_grant(deps, env, info, grant_id),
+        ExecuteMsg::PauseGrant { grant_id } => execute_pause_grant(deps, env, info, grant_id),
+        ExecuteMsg::ResumeGrant { grant_id } => execute_resume_grant(deps, env, info, grant_id),
         ExecuteMsg::Claim { grant_id } => execute_claim(deps, env, info, grant_id),
+        ExecuteMsg::ClaimAll { dao } => execute_claim_all(deps, env, info, dao),
+        ExecuteMsg::SetGrantSplit {
+            grant_id,
+            recipients,
+        } => execute_set_grant_split(deps, env, info, grant_id, recipients),
+        ExecuteMsg::SubmitMilestoneReport {
+            grant_id,
+            milestone,
+            report_hash,
+        } => execute_submit_milestone_report(deps, env, info, grant_id, milestone, report_hash),
+        ExecuteMsg::ApproveMilestone {
+            grant_id,
+            milestone,
+        } => execute_approve_milestone(deps, env, info, grant_id, milestone),
     }
 }
 
+/// The fields of `ExecuteMsg::AddGrant`
+pub struct GrantParams {
+    pub dao: Addr,
+    pub duration: u64,
+    pub amount: Uint128,
+    pub vesting: Option<VestingSchedule>,
+    pub asset: Option<Asset>,
+    pub start: Option<Timestamp>,
+}
+
 pub fn execute_add_grant(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    dao: Addr,
-    duration: u64,
-    amount: Uint128,
+    params: GrantParams,
 ) -> Result<Response, ContractError> {
+    let GrantParams {
+        dao,
+        duration,
+        amount,
+        vesting,
+        asset,
+        start,
+    } = params;
     let config = CONFIG.load(deps.storage)?;
 
     // Only the governance contract can add grants via a funding proposal
@@ -152,16 +273,76 @@ pub fn execute_add_grant(
         return Err(ContractError::Unauthorized {});
     }
 
+    let vesting = vesting.unwrap_or_default();
+    vesting
+        .validate(duration, amount)
+        .map_err(|err| ContractError::InvalidVestingSchedule {
+            error: err.to_string(),
+        })?;
+
+    let asset = match asset.unwrap_or_default() {
+        Asset::Cw20 { contract_addr } => Asset::Cw20 {
+            contract_addr: deps.api.addr_validate(contract_addr.as_str())?,
+        },
+        asset => asset,
+    };
+
+    let milestones = match &vesting {
+        VestingSchedule::Milestones { amounts, .. } => {
+            vec![MilestoneProgress::default(); amounts.len()]
+        }
+        _ => vec![],
+    };
+
+    let vesting = match vesting {
+        VestingSchedule::Milestones {
+            approver: Approver::Reviewer { address },
+            amounts,
+        } => VestingSchedule::Milestones {
+            approver: Approver::Reviewer {
+                address: deps.api.addr_validate(address.as_str())?,
+            },
+            amounts,
+        },
+        vesting => vesting,
+    };
+
+    // Grants can't commit more than the treasury holds on top of the open grants
+    let balance = asset.query_balance(&deps.querier, &env.contract.address)?;
+    let committed = committed_amount(deps.storage, &asset)?;
+    let free = balance.saturating_sub(committed);
+
+    if amount > free {
+        return Err(ContractError::InsufficientTreasury {
+            asset: asset.to_string(),
+            free,
+        });
+    }
+
+    update_commitment(deps.storage, &asset, |committed| {
+        Ok(committed.checked_add(amount)?)
+    })?;
+
+    // Grants can't start vesting in the past
+    let started = start.unwrap_or(env.block.time).max(env.block.time);
+
     let grant = Grant {
         grant_id: Grant::next_id(deps.storage)?,
         dao: dao.clone(),
         amount_approved: amount,
         amount_remaining: amount,
-        started: env.block.time,
-        expires: env.block.time.plus_seconds(duration),
+        started,
+        expires: started.plus_seconds(duration),
+        revoked: false,
+        vesting,
+        asset,
+        recipients: vec![],
+        milestones,
+        paused_at: None,
+        paused_duration: 0,
     };
 
-    grants().save(deps.storage, grant.grant_id.to_string(), &grant)?;
+    grants().save(deps.storage, grant.grant_id, &grant)?;
 
     Ok(Response::new()
         .add_attribute("grant_id", grant.grant_id.to_string())
@@ -170,6 +351,7 @@ pub fn execute_add_grant(
         .add_attribute("amount_remaining", grant.amount_remaining.to_string())
         .add_attribute("started", grant.started.to_string())
         .add_attribute("expires", grant.expires.to_string())
+        .add_attribute("asset", grant.asset.to_string())
         .add_attribute("owner", config.owner)
         .add_attribute("sender", info.sender))
 }
@@ -182,7 +364,7 @@ pub fn execute_claim(
 ) -> Result<Response, ContractError> {
     // Anyone can claim, they are doing us a favor by paying the tx fee
 
-    let maybe_grant = grants().may_load(deps.storage, grant_id.to_string())?;
+    let maybe_grant = grants().may_load(deps.storage, grant_id)?;
 
     if maybe_grant.is_none() {
         return Err(ContractError::GrantNotFound {});
@@ -190,15 +372,19 @@ pub fn execute_claim(
 
     let mut grant = maybe_grant.unwrap();
 
-    if grant.amount_remaining.is_zero() {
-        return Err(ContractError::AlreadyClaimed {});
+    if grant.revoked {
+        return Err(ContractError::GrantRevoked {});
     }
 
-    let claimable_amount = claimable_amount(env.block.time, &grant);
+    if grant.paused_at.is_some() {
+        return Err(ContractError::GrantPaused {});
+    }
 
-    grant.amount_remaining = grant.amount_remaining.sub(claimable_amount);
+    if grant.amount_remaining.is_zero() {
+        return Err(ContractError::AlreadyClaimed {});
+    }
 
-    grants().save(deps.storage, grant.grant_id.to_string(), &grant)?;
+    let claimable_amount = claim_grant(deps.storage, env.block.time, &mut grant)?;
 
     // TODO returning a contract error throws undescript error 400
     if claimable_amount < MIN_CLAIMABLE_AMOUNT {
@@ -206,30 +392,586 @@ pub fn execute_claim(
     }
 
     Ok(Response::new()
-        .add_message(BankMsg::Send {
-            to_address: grant.dao.to_string(),
-            amount: vec![Coin {
-                denom: "uluna".to_string(),
-                amount: claimable_amount,
-            }],
-        })
+        .add_messages(payout_msgs(&grant, claimable_amount)?)
+        .add_attribute("asset", grant.asset.to_string())
         .add_attribute("claimable_amount", claimable_amount)
         .add_attribute("amount_remaining", grant.amount_remaining))
 }
 
-fn claimable_amount(block_time: Timestamp, grant: &Grant) -> Uint128 {
-    let time_passed = block_time.minus_nanos(grant.started.nanos()).seconds(); // time since start of grant
-    let duration = grant.expires.minus_nanos(grant.started.nanos()).seconds(); // lifespan of grant
+pub fn execute_claim_all(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    dao: Addr,
+) -> Result<Response, ContractError> {
+    // Anyone can claim, they are doing us a favor by paying the tx fee
+
+    let dao_grants = grants()
+        .idx
+        .dao
+        .prefix(dao.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, grant)| grant))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "claim_all")
+        .add_attribute("dao", dao.to_string());
+
+    // Claimed amounts are combined into one transfer per asset and recipient
+    let mut claimed: Vec<(Asset, Uint128)> = vec![];
+    let mut payouts: Vec<(Asset, Addr, Uint128)> = vec![];
+
+    for mut grant in dao_grants {
+        if grant.revoked || grant.paused_at.is_some() || grant.amount_remaining.is_zero() {
+            continue;
+        }
+
+        if claimable_amount(env.block.time, &grant) < MIN_CLAIMABLE_AMOUNT {
+            continue;
+        }
+
+        let claimed_amount = claim_grant(deps.storage, env.block.time, &mut grant)?;
+
+        response = response.add_attribute(
+            format!("grant_{}_claimed_amount", grant.grant_id),
+            claimed_amount,
+        );
+
+        for (recipient, share) in grant.payout_shares(claimed_amount)? {
+            match payouts
+                .iter_mut()
+                .find(|(asset, addr, _)| *asset == grant.asset && *addr == recipient)
+            {
+                Some((_, _, total)) => *total = total.checked_add(share).map_err(StdError::from)?,
+                None => payouts.push((grant.asset.clone(), recipient, share)),
+            }
+        }
+
+        match claimed.iter_mut().find(|(asset, _)| *asset == grant.asset) {
+            Some((_, total)) => {
+                *total = total.checked_add(claimed_amount).map_err(StdError::from)?
+            }
+            None => claimed.push((grant.asset, claimed_amount)),
+        }
+    }
+
+    if claimed.is_empty() {
+        return Err(ContractError::AmountTooSmall {});
+    }
+
+    for (asset, recipient, amount) in payouts {
+        response = response.add_message(asset.transfer_msg(&recipient, amount)?);
+    }
+
+    for (asset, amount) in claimed {
+        response = response.add_attribute(format!("{}_claimed_amount", asset), amount);
+    }
+
+    Ok(response)
+}
+
+pub fn execute_set_grant_split(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+    recipients: Vec<Recipient>,
+) -> Result<Response, ContractError> {
+    let maybe_grant = grants().may_load(deps.storage, grant_id)?;
+
+    if maybe_grant.is_none() {
+        return Err(ContractError::GrantNotFound {});
+    }
+
+    let mut grant = maybe_grant.unwrap();
+
+    // Only the grantee dao can decide who receives its funding
+    if grant.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if grant.revoked {
+        return Err(ContractError::GrantRevoked {});
+    }
+
+    if recipients.len() > MAX_SPLIT_RECIPIENTS {
+        return Err(ContractError::InvalidSplit {
+            error: format!("At most {} recipients allowed", MAX_SPLIT_RECIPIENTS),
+        });
+    }
+
+    let mut validated: Vec<Recipient> = vec![];
+    for recipient in recipients {
+        if recipient.weight == 0 {
+            return Err(ContractError::InvalidSplit {
+                error: "Recipient weights must be positive".to_string(),
+            });
+        }
+
+        if recipient.weight > MAX_SPLIT_WEIGHT {
+            return Err(ContractError::InvalidSplit {
+                error: format!("Recipient weights can be at most {}", MAX_SPLIT_WEIGHT),
+            });
+        }
+
+        let address = deps.api.addr_validate(recipient.address.as_str())?;
+        if validated.iter().any(|r| r.address == address) {
+            return Err(ContractError::InvalidSplit {
+                error: format!("Duplicate recipient {}", address),
+            });
+        }
+
+        validated.push(Recipient {
+            address,
+            weight: recipient.weight,
+        });
+    }
+
+    grant.recipients = validated;
+    grants().save(deps.storage, grant.grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_grant_split")
+        .add_attribute("grant_id", grant.grant_id.to_string())
+        .add_attribute("recipients", grant.recipients.len().to_string()))
+}
+
+pub fn execute_submit_milestone_report(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+    milestone: u32,
+    report_hash: String,
+) -> Result<Response, ContractError> {
+    let mut grant = load_milestone_grant(deps.as_ref(), grant_id)?;
+
+    // Only the grantee dao reports on its milestones
+    if grant.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Same format as the governance proposal content hash
+    let report_hash = report_hash.trim().to_lowercase();
+    if report_hash.len() != 64 || !report_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidReportHash {});
+    }
+
+    let progress = grant
+        .milestones
+        .get_mut(milestone as usize)
+        .ok_or(ContractError::MilestoneNotFound {})?;
+
+    if progress.approved {
+        return Err(ContractError::MilestoneAlreadyApproved {});
+    }
+
+    progress.report_hash = Some(report_hash.clone());
+    grants().save(deps.storage, grant.grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_milestone_report")
+        .add_attribute("grant_id", grant.grant_id.to_string())
+        .add_attribute("milestone", milestone.to_string())
+        .add_attribute("report_hash", report_hash))
+}
+
+pub fn execute_approve_milestone(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+    milestone: u32,
+) -> Result<Response, ContractError> {
+    let mut grant = load_milestone_grant(deps.as_ref(), grant_id)?;
+
+    let approver = match &grant.vesting {
+        VestingSchedule::Milestones { approver, .. } => approver.clone(),
+        _ => return Err(ContractError::NotMilestoneGrant {}),
+    };
 
-    let matured_permille = (time_passed * 1000 / duration).min(1000); // Max pay out 1000/permille
-    let matured_amount = grant // Total amount matured: 'amount * time since start'/'lifespan'
-        .amount_approved
-        .mul(Decimal::permille(matured_permille));
+    let config = CONFIG.load(deps.storage)?;
+
+    let approver_addr = match approver {
+        Approver::Governance {} => Some(config.owner),
+        Approver::Reviewer { address } => Some(address),
+        Approver::CoreSlot { slot } => {
+            // The governance contract keeps track of the core slot holders
+            let core_slots: GovernanceCoreSlotsResponse = deps
+                .querier
+                .query_wasm_smart(config.owner, &GovernanceQueryMsg::CoreSlots {})?;
+            match slot {
+                CoreSlot::Brand {} => core_slots.brand,
+                CoreSlot::CoreTech {} => core_slots.core_tech,
+                CoreSlot::Creative {} => core_slots.creative,
+            }
+            .map(|holder| holder.dao)
+        }
+    };
+
+    if approver_addr != Some(info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let progress = grant
+        .milestones
+        .get_mut(milestone as usize)
+        .ok_or(ContractError::MilestoneNotFound {})?;
+
+    if progress.approved {
+        return Err(ContractError::MilestoneAlreadyApproved {});
+    }
+
+    if progress.report_hash.is_none() {
+        return Err(ContractError::MilestoneReportMissing {});
+    }
+
+    progress.approved = true;
+    grants().save(deps.storage, grant.grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_milestone")
+        .add_attribute("grant_id", grant.grant_id.to_string())
+        .add_attribute("milestone", milestone.to_string())
+        .add_attribute("approver", info.sender))
+}
+
+fn load_milestone_grant(deps: Deps, grant_id: u64) -> Result<Grant, ContractError> {
+    let grant = grants()
+        .may_load(deps.storage, grant_id)?
+        .ok_or(ContractError::GrantNotFound {})?;
+
+    if grant.revoked {
+        return Err(ContractError::GrantRevoked {});
+    }
+
+    match grant.vesting {
+        VestingSchedule::Milestones { .. } => Ok(grant),
+        _ => Err(ContractError::NotMilestoneGrant {}),
+    }
+}
+
+// Transfers of a payout to the grant's recipients
+fn payout_msgs(grant: &Grant, amount: Uint128) -> StdResult<Vec<CosmosMsg>> {
+    grant
+        .payout_shares(amount)?
+        .into_iter()
+        .map(|(recipient, share)| grant.asset.transfer_msg(&recipient, share))
+        .collect()
+}
+
+// Moves the vested amount of the grant out of the treasury commitments
+fn claim_grant(
+    storage: &mut dyn Storage,
+    block_time: Timestamp,
+    grant: &mut Grant,
+) -> StdResult<Uint128> {
+    let claimable_amount = claimable_amount(block_time, grant);
+
+    grant.amount_remaining = grant.amount_remaining.sub(claimable_amount);
+
+    grants().save(storage, grant.grant_id, grant)?;
+    update_commitment(storage, &grant.asset, |committed| {
+        Ok(committed.saturating_sub(claimable_amount))
+    })?;
+
+    Ok(claimable_amount)
+}
+
+pub fn execute_revoke_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the governance contract can revoke grants via a proposal
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let maybe_grant = grants().may_load(deps.storage, grant_id)?;
+
+    if maybe_grant.is_none() {
+        return Err(ContractError::GrantNotFound {});
+    }
+
+    let mut grant = maybe_grant.unwrap();
+
+    if grant.revoked {
+        return Err(ContractError::GrantRevoked {});
+    }
+
+    // The dao keeps what has vested so far, the rest stays in the treasury
+    let vested_amount = claimable_amount(env.block.time, &grant);
+    let returned_amount = grant.amount_remaining.sub(vested_amount);
+
+    // Both the paid out and the returned amount are no longer committed
+    let released_amount = grant.amount_remaining;
+    update_commitment(deps.storage, &grant.asset, |committed| {
+        Ok(committed.saturating_sub(released_amount))
+    })?;
+
+    grant.amount_remaining = Uint128::zero();
+    grant.revoked = true;
+
+    grants().save(deps.storage, grant.grant_id, &grant)?;
+
+    let mut response = Response::new();
+
+    if !vested_amount.is_zero() {
+        response = response.add_messages(payout_msgs(&grant, vested_amount)?);
+    }
+
+    Ok(response
+        .add_attribute("action", "revoke_grant")
+        .add_attribute("grant_id", grant.grant_id.to_string())
+        .add_attribute("dao", grant.dao.to_string())
+        .add_attribute("asset", grant.asset.to_string())
+        .add_attribute("vested_amount", vested_amount)
+        .add_attribute("returned_amount", returned_amount))
+}
+
+pub fn execute_pause_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the governance contract can pause grants via a proposal
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut grant = grants()
+        .may_load(deps.storage, grant_id)?
+        .ok_or(ContractError::GrantNotFound {})?;
+
+    if grant.revoked {
+        return Err(ContractError::GrantRevoked {});
+    }
+
+    if grant.paused_at.is_some() {
+        return Err(ContractError::GrantPaused {});
+    }
+
+    grant.paused_at = Some(env.block.time);
+    grants().save(deps.storage, grant.grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause_grant")
+        .add_attribute("grant_id", grant.grant_id.to_string())
+        .add_attribute("dao", grant.dao.to_string()))
+}
+
+pub fn execute_resume_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grant_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the governance contract can resume grants via a proposal
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut grant = grants()
+        .may_load(deps.storage, grant_id)?
+        .ok_or(ContractError::GrantNotFound {})?;
+
+    if grant.revoked {
+        return Err(ContractError::GrantRevoked {});
+    }
+
+    let paused_at = grant.paused_at.ok_or(ContractError::GrantNotPaused {})?;
+
+    // Vesting picks up where it left off, so the grant runs that much longer. A queued grant
+    // only loses the time paused after it started vesting.
+    let paused_seconds =
+        env.block.time.max(grant.started).seconds() - paused_at.max(grant.started).seconds();
+    grant.paused_duration += paused_seconds;
+    grant.expires = grant.expires.plus_seconds(paused_seconds);
+    grant.paused_at = None;
+
+    grants().save(deps.storage, grant.grant_id, &grant)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "resume_grant")
+        .add_attribute("grant_id", grant.grant_id.to_string())
+        .add_attribute("dao", grant.dao.to_string())
+        .add_attribute("paused_seconds", paused_seconds.to_string())
+        .add_attribute("expires", grant.expires.seconds().to_string()))
+}
+
+// Amount vested but not yet paid out, shared by claims, revocations and queries
+fn claimable_amount(block_time: Timestamp, grant: &Grant) -> Uint128 {
+    if grant.revoked {
+        return Uint128::zero();
+    }
 
     let already_claimed = grant.amount_approved.sub(grant.amount_remaining);
-    let claimable_amount = matured_amount.sub(already_claimed);
 
-    return claimable_amount;
+    grant
+        .vested_amount(block_time)
+        .saturating_sub(already_claimed)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Grants used to be keyed by their id as string. Numeric keys start with a zero byte
+    // for any reachable id, string keys with a digit.
+    let legacy = legacy_grants()
+        .range_raw(deps.storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((key, _)) if key.first() == Some(&0)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (key, grant) in legacy.iter() {
+        legacy_grants().remove(
+            deps.storage,
+            String::from_utf8(key.clone()).map_err(StdError::from)?,
+        )?;
+        grants().save(deps.storage, grant.grant_id, grant)?;
+    }
+
+    // Rebuild the commitments, grants added before they were tracked aren't counted yet
+    let assets = COMMITMENTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for asset in assets {
+        COMMITMENTS.remove(deps.storage, asset);
+    }
+
+    let all_grants = grants()
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, grant) in all_grants {
+        update_commitment(deps.storage, &grant.asset, |committed| {
+            Ok(committed.checked_add(grant.amount_remaining)?)
+        })?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("rekeyed_grants", legacy.len().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    fn legacy_grant(grant_id: u64, dao: &str, amount_remaining: u128) -> Grant {
+        Grant {
+            grant_id,
+            dao: Addr::unchecked(dao),
+            amount_approved: Uint128::new(1000),
+            amount_remaining: Uint128::new(amount_remaining),
+            started: Timestamp::from_seconds(0),
+            expires: Timestamp::from_seconds(1000),
+            revoked: false,
+            vesting: VestingSchedule::default(),
+            asset: Asset::default(),
+            recipients: vec![],
+            milestones: vec![],
+            paused_at: None,
+            paused_duration: 0,
+        }
+    }
+
+    #[test]
+    fn migrate_rekeys_legacy_grants() {
+        let mut deps = mock_dependencies();
+
+        for (grant_id, dao, amount_remaining) in
+            [(1, "dao1", 1000), (2, "dao2", 400), (10, "dao1", 0)]
+        {
+            let grant = legacy_grant(grant_id, dao, amount_remaining);
+            legacy_grants()
+                .save(deps.as_mut().storage, grant_id.to_string(), &grant)
+                .unwrap();
+        }
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let ids = grants()
+            .keys(deps.as_ref().storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids, vec![1, 2, 10]);
+
+        let dao1_ids = grants()
+            .idx
+            .dao
+            .prefix(Addr::unchecked("dao1"))
+            .keys(deps.as_ref().storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(dao1_ids, vec![1, 10]);
+
+        assert_eq!(
+            committed_amount(deps.as_ref().storage, &Asset::default()).unwrap(),
+            Uint128::new(1400)
+        );
+
+        // Migrating again leaves the grants and commitments as they are
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        assert_eq!(
+            grants()
+                .load(deps.as_ref().storage, 2)
+                .unwrap()
+                .amount_remaining,
+            Uint128::new(400)
+        );
+        assert_eq!(
+            committed_amount(deps.as_ref().storage, &Asset::default()).unwrap(),
+            Uint128::new(1400)
+        );
+    }
+
+    #[test]
+    fn pause_before_start_keeps_vesting_time() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("governance", &[]);
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("governance"),
+            identityservice_contract: Addr::unchecked("identityservice"),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // A queued grant starting 100 seconds from now
+        let now = mock_env().block.time;
+        let started = now.plus_seconds(100);
+        let grant = Grant {
+            started,
+            expires: started.plus_seconds(1000),
+            ..legacy_grant(1, "dao1", 1000)
+        };
+        grants().save(deps.as_mut().storage, 1, &grant).unwrap();
+
+        execute_pause_grant(deps.as_mut(), mock_env(), info.clone(), 1).unwrap();
+
+        // Resume 50 seconds after the grant started
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(150);
+        execute_resume_grant(deps.as_mut(), env, info, 1).unwrap();
+
+        let grant = grants().load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(grant.paused_duration, 50);
+        assert_eq!(grant.expires, started.plus_seconds(1050));
+        assert_eq!(
+            grant.vested_amount(started.plus_seconds(550)),
+            Uint128::new(500)
+        );
+    }
 }
 
 // #[cfg(test)]
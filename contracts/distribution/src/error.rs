@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -17,4 +17,37 @@ pub enum ContractError {
 
     #[error("AlreadyClaimed")]
     AlreadyClaimed {},
+
+    #[error("GrantRevoked")]
+    GrantRevoked {},
+
+    #[error("GrantPaused")]
+    GrantPaused {},
+
+    #[error("GrantNotPaused")]
+    GrantNotPaused {},
+
+    #[error("InsufficientTreasury: only {free} {asset} is free")]
+    InsufficientTreasury { asset: String, free: Uint128 },
+
+    #[error("NotMilestoneGrant")]
+    NotMilestoneGrant {},
+
+    #[error("MilestoneNotFound")]
+    MilestoneNotFound {},
+
+    #[error("MilestoneReportMissing")]
+    MilestoneReportMissing {},
+
+    #[error("MilestoneAlreadyApproved")]
+    MilestoneAlreadyApproved {},
+
+    #[error("InvalidReportHash")]
+    InvalidReportHash {},
+
+    #[error("InvalidSplit: {error}")]
+    InvalidSplit { error: String },
+
+    #[error("InvalidVestingSchedule: {error}")]
+    InvalidVestingSchedule { error: String },
 }
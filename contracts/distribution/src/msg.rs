@@ -2,7 +2,7 @@ use cosmwasm_std::{Addr, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::Grant;
+use crate::state::{Asset, GrantStatus, MilestoneProgress, Recipient, VestingSchedule};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -10,6 +10,9 @@ pub struct InstantiateMsg {
     pub identityservice_contract: Addr,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -17,13 +20,46 @@ pub enum ExecuteMsg {
         dao: Addr,
         duration: u64,
         amount: Uint128,
+        // Defaults to linear vesting
+        vesting: Option<VestingSchedule>,
+        // Defaults to uluna
+        asset: Option<Asset>,
+        // Delays the start of vesting, e.g. for grants queued to a later funding cycle
+        start: Option<Timestamp>,
+    },
+    RevokeGrant {
+        grant_id: u64,
+    },
+    // Freezes vesting and claims of a grant until it is resumed
+    PauseGrant {
+        grant_id: u64,
+    },
+    ResumeGrant {
+        grant_id: u64,
     },
-    // RevokeGrant {
-    //     dao: Addr,
-    // },
     Claim {
         grant_id: u64,
     },
+    // Claims all grants of the dao with a vested amount over the minimum
+    ClaimAll {
+        dao: Addr,
+    },
+    // Splits the payouts of a grant across recipients, only callable by the grantee dao
+    SetGrantSplit {
+        grant_id: u64,
+        recipients: Vec<Recipient>,
+    },
+    // Grantee dao reports a milestone as delivered
+    SubmitMilestoneReport {
+        grant_id: u64,
+        milestone: u32,
+        report_hash: String,
+    },
+    // Approver signs off on a reported milestone, releasing its amount
+    ApproveMilestone {
+        grant_id: u64,
+        milestone: u32,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -35,9 +71,11 @@ pub enum QueryMsg {
     },
     Grants {
         dao: Option<Addr>,
-        start_after: Option<String>,
+        status: Option<GrantStatus>,
+        start_after: Option<u64>,
         limit: Option<u32>,
     },
+    Treasury {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -54,9 +92,51 @@ pub struct GrantResponse {
     pub amount_remaining: Uint128,
     pub started: Timestamp,
     pub expires: Timestamp,
+    pub revoked: bool,
+    pub vesting: VestingSchedule,
+    pub asset: Asset,
+    pub recipients: Vec<Recipient>,
+    pub milestones: Vec<MilestoneProgress>,
+    pub paused_at: Option<Timestamp>,
+    pub paused_duration: u64,
+    pub status: GrantStatus,
     pub claimable_amount: Uint128,
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GrantsResponse {
-    pub grants: Vec<Grant>,
+    pub grants: Vec<GrantResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryAsset {
+    pub asset: Asset,
+    pub balance: Uint128,
+    // Granted but not yet paid out
+    pub committed: Uint128,
+    // Available for new grants
+    pub free: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryResponse {
+    pub assets: Vec<TreasuryAsset>,
+}
+
+// Subset of the governance contract's queries used to look up core slot holders
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceQueryMsg {
+    CoreSlots {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CoreSlotHolder {
+    pub dao: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GovernanceCoreSlotsResponse {
+    pub brand: Option<CoreSlotHolder>,
+    pub creative: Option<CoreSlotHolder>,
+    pub core_tech: Option<CoreSlotHolder>,
 }
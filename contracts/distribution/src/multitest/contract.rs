@@ -5,7 +5,10 @@ use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 use crate::contract::{execute, instantiate, query};
 use crate::ContractError;
 // use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::msg::{
+    ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, QueryMsg, TreasuryResponse,
+};
+use crate::state::{GrantStatus, Recipient};
 
 #[derive(Debug, Clone)]
 pub struct DistributionContract(Addr);
@@ -62,6 +65,116 @@ impl DistributionContract {
         .map_err(|err| err.downcast().unwrap())
         // .map(|_| ())
     }
+
+    #[track_caller]
+    pub fn claim_all(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        dao: &Addr,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::ClaimAll { dao: dao.clone() },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn set_grant_split(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        grant_id: u64,
+        recipients: Vec<Recipient>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::SetGrantSplit {
+                grant_id,
+                recipients,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn submit_milestone_report(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        grant_id: u64,
+        milestone: u32,
+        report_hash: &str,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::SubmitMilestoneReport {
+                grant_id,
+                milestone,
+                report_hash: report_hash.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn approve_milestone(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        grant_id: u64,
+        milestone: u32,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::ApproveMilestone {
+                grant_id,
+                milestone,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn query_grant(&self, app: &mut App, grant_id: u64) -> StdResult<Option<GrantResponse>> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Grant { grant_id })
+    }
+
+    #[track_caller]
+    pub fn query_grants(
+        &self,
+        app: &mut App,
+        dao: Option<Addr>,
+        status: Option<GrantStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<GrantsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Grants {
+                dao,
+                status,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_treasury(&self, app: &mut App) -> StdResult<TreasuryResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Treasury {})
+    }
 }
 
 impl From<DistributionContract> for Addr {
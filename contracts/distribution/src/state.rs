@@ -1,8 +1,12 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, StdResult, Storage, Timestamp, Uint128};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, CosmosMsg, Decimal, QuerierWrapper, StdError, StdResult,
+    Storage, Timestamp, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -13,6 +17,219 @@ pub struct Config {
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const GRANT_COUNT: Item<u64> = Item::new("grant_count");
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Commitment {
+    pub asset: Asset,
+    pub amount: Uint128,
+}
+
+// Amounts granted but not yet paid out, keyed by asset
+pub const COMMITMENTS: Map<String, Commitment> = Map::new("commitments");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Asset {
+    Native { denom: String },
+    Cw20 { contract_addr: Addr },
+}
+
+impl Default for Asset {
+    fn default() -> Self {
+        Asset::Native {
+            denom: "uluna".to_string(),
+        }
+    }
+}
+
+impl Asset {
+    /// Balance of the asset held by `address`
+    pub fn query_balance(&self, querier: &QuerierWrapper, address: &Addr) -> StdResult<Uint128> {
+        match self {
+            Asset::Native { denom } => Ok(querier.query_balance(address, denom)?.amount),
+            Asset::Cw20 { contract_addr } => {
+                let balance: BalanceResponse = querier.query_wasm_smart(
+                    contract_addr,
+                    &Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )?;
+                Ok(balance.balance)
+            }
+        }
+    }
+
+    /// Msg paying `amount` of the asset to `recipient`
+    pub fn transfer_msg(&self, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        Ok(match self {
+            Asset::Native { denom } => CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount.u128(), denom),
+            }),
+            Asset::Cw20 { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }),
+        })
+    }
+}
+
+impl std::fmt::Display for Asset {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Asset::Native { denom } => write!(f, "{}", denom),
+            Asset::Cw20 { contract_addr } => write!(f, "cw20:{}", contract_addr),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Tranche {
+    // Seconds after the start of the grant
+    pub release_after: u64,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum VestingSchedule {
+    // Vests linearly in permille steps between started and expires
+    Linear {},
+    // Nothing vests before the cliff, after it the linearly vested amount is released
+    Cliff {
+        cliff: u64,
+    },
+    // Fixed amounts released at fixed times
+    Tranches {
+        tranches: Vec<Tranche>,
+    },
+    // Equal amounts released every interval seconds
+    Periodic {
+        interval: u64,
+    },
+    // Amounts released once the approver signs off on the milestone report
+    Milestones {
+        approver: Approver,
+        amounts: Vec<Uint128>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CoreSlot {
+    Brand {},
+    CoreTech {},
+    Creative {},
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Approver {
+    // The governance contract, via a proposal
+    Governance {},
+    // The dao currently holding the core slot
+    CoreSlot { slot: CoreSlot },
+    Reviewer { address: Addr },
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct MilestoneProgress {
+    // Hex encoded sha256 hash of the grantee's report
+    pub report_hash: Option<String>,
+    pub approved: bool,
+}
+
+impl Default for VestingSchedule {
+    fn default() -> Self {
+        VestingSchedule::Linear {}
+    }
+}
+
+impl VestingSchedule {
+    pub fn validate(&self, duration: u64, amount: Uint128) -> StdResult<()> {
+        match self {
+            VestingSchedule::Linear {} => {}
+            VestingSchedule::Cliff { cliff } => {
+                if *cliff > duration {
+                    return Err(StdError::generic_err(
+                        "Cliff must not be longer than the grant duration",
+                    ));
+                }
+            }
+            VestingSchedule::Tranches { tranches } => {
+                if tranches.is_empty() {
+                    return Err(StdError::generic_err("At least one tranche is required"));
+                }
+                if tranches.iter().any(|t| t.release_after > duration) {
+                    return Err(StdError::generic_err(
+                        "Tranches must be released within the grant duration",
+                    ));
+                }
+                let total = tranches
+                    .iter()
+                    .try_fold(Uint128::zero(), |total, t| total.checked_add(t.amount))?;
+                if total != amount {
+                    return Err(StdError::generic_err(
+                        "Tranches must add up to the grant amount",
+                    ));
+                }
+            }
+            VestingSchedule::Periodic { interval } => {
+                if *interval == 0 || *interval > duration {
+                    return Err(StdError::generic_err(
+                        "Interval must be between 1 second and the grant duration",
+                    ));
+                }
+            }
+            VestingSchedule::Milestones { amounts, .. } => {
+                if amounts.is_empty() {
+                    return Err(StdError::generic_err("At least one milestone is required"));
+                }
+                let total = amounts
+                    .iter()
+                    .try_fold(Uint128::zero(), |total, amount| total.checked_add(*amount))?;
+                if total != amount {
+                    return Err(StdError::generic_err(
+                        "Milestones must add up to the grant amount",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maximum number of recipients a grant payout can be split across
+pub const MAX_SPLIT_RECIPIENTS: usize = 10;
+
+/// Maximum weight of a single split recipient
+pub const MAX_SPLIT_WEIGHT: u64 = 1_000_000;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Recipient {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantStatus {
+    // Still vesting
+    Active,
+    // Fully vested but not fully claimed
+    Expired,
+    FullyClaimed,
+    Revoked,
+    // Vesting is frozen and nothing can be claimed until resumed
+    Paused,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct Grant {
     pub grant_id: u64,
@@ -21,6 +238,25 @@ pub struct Grant {
     pub amount_remaining: Uint128,
     pub started: Timestamp,
     pub expires: Timestamp,
+    // Revoked grants pay out nothing more, the unvested amount stays in the treasury
+    #[serde(default)]
+    pub revoked: bool,
+    #[serde(default)]
+    pub vesting: VestingSchedule,
+    #[serde(default)]
+    pub asset: Asset,
+    // Payouts are split across the recipients by weight, empty pays the dao
+    #[serde(default)]
+    pub recipients: Vec<Recipient>,
+    // Reports and approvals of a milestone grant, one per milestone
+    #[serde(default)]
+    pub milestones: Vec<MilestoneProgress>,
+    // Set while the grant is paused, vesting time stands still from this moment
+    #[serde(default)]
+    pub paused_at: Option<Timestamp>,
+    // Total seconds spent paused, the expiry has been extended by as much
+    #[serde(default)]
+    pub paused_duration: u64,
 }
 
 impl Grant {
@@ -29,11 +265,100 @@ impl Grant {
         GRANT_COUNT.save(store, &id)?;
         Ok(id)
     }
+
+    pub fn status(&self, block_time: Timestamp) -> GrantStatus {
+        if self.revoked {
+            GrantStatus::Revoked
+        } else if self.paused_at.is_some() {
+            GrantStatus::Paused
+        } else if self.amount_remaining.is_zero() {
+            GrantStatus::FullyClaimed
+        } else if block_time >= self.expires {
+            GrantStatus::Expired
+        } else {
+            GrantStatus::Active
+        }
+    }
+
+    /// Shares of a payout of `amount`, rounding dust goes to the last recipient
+    pub fn payout_shares(&self, amount: Uint128) -> StdResult<Vec<(Addr, Uint128)>> {
+        if self.recipients.is_empty() {
+            return Ok(vec![(self.dao.clone(), amount)]);
+        }
+
+        let total_weight = self
+            .recipients
+            .iter()
+            .try_fold(0u128, |total, r| total.checked_add(r.weight.into()))
+            .ok_or_else(|| StdError::generic_err("Split weights overflow"))?;
+        let mut remaining = amount;
+
+        let mut shares: Vec<(Addr, Uint128)> = self
+            .recipients
+            .iter()
+            .map(|recipient| {
+                let share = amount.multiply_ratio(recipient.weight, total_weight);
+                remaining -= share;
+                (recipient.address.clone(), share)
+            })
+            .collect();
+
+        if let Some((_, last_share)) = shares.last_mut() {
+            *last_share += remaining;
+        }
+
+        shares.retain(|(_, share)| !share.is_zero());
+        Ok(shares)
+    }
+
+    /// Total amount vested at `block_time` according to the grant's vesting schedule
+    pub fn vested_amount(&self, block_time: Timestamp) -> Uint128 {
+        // Time spent paused doesn't count towards vesting
+        let vesting_time = self.paused_at.unwrap_or(block_time).min(block_time);
+        let time_passed = vesting_time
+            .seconds()
+            .saturating_sub(self.started.seconds())
+            .saturating_sub(self.paused_duration); // time since start of grant
+        let duration = self.expires.seconds() - self.started.seconds() - self.paused_duration; // lifespan of grant
+
+        match &self.vesting {
+            // Milestones only vest on approval, however long the grant runs
+            VestingSchedule::Milestones { amounts, .. } => amounts
+                .iter()
+                .zip(self.milestones.iter())
+                .filter(|(_, progress)| progress.approved)
+                .map(|(amount, _)| *amount)
+                .sum(),
+            _ if time_passed >= duration => self.amount_approved,
+            VestingSchedule::Linear {} => self.linear_vested_amount(time_passed, duration),
+            VestingSchedule::Cliff { cliff } => {
+                if time_passed < *cliff {
+                    Uint128::zero()
+                } else {
+                    self.linear_vested_amount(time_passed, duration)
+                }
+            }
+            VestingSchedule::Tranches { tranches } => tranches
+                .iter()
+                .filter(|tranche| tranche.release_after <= time_passed)
+                .map(|tranche| tranche.amount)
+                .sum(),
+            VestingSchedule::Periodic { interval } => {
+                let released_time = time_passed / interval * interval;
+                self.amount_approved.multiply_ratio(released_time, duration)
+            }
+        }
+    }
+
+    fn linear_vested_amount(&self, time_passed: u64, duration: u64) -> Uint128 {
+        let matured_permille = (time_passed * 1000 / duration).min(1000); // Max pay out 1000/permille
+        self.amount_approved * Decimal::permille(matured_permille) // 'amount * time since start'/'lifespan'
+    }
 }
 
 pub struct GrantIndexes<'a> {
     // pk goes to second tuple element
-    pub dao: MultiIndex<'a, Addr, Grant, String>,
+    pub dao: MultiIndex<'a, Addr, Grant, u64>,
 }
 
 impl<'a> IndexList<Grant> for GrantIndexes<'a> {
@@ -43,7 +368,7 @@ impl<'a> IndexList<Grant> for GrantIndexes<'a> {
     }
 }
 
-pub fn grants<'a>() -> IndexedMap<'a, String, Grant, GrantIndexes<'a>> {
+pub fn grants<'a>() -> IndexedMap<'a, u64, Grant, GrantIndexes<'a>> {
     let indexes = GrantIndexes {
         dao: MultiIndex::new(
             |_pk: &[u8], d: &Grant| d.dao.clone(),
@@ -53,3 +378,26 @@ pub fn grants<'a>() -> IndexedMap<'a, String, Grant, GrantIndexes<'a>> {
     };
     IndexedMap::new("grants", indexes)
 }
+
+pub struct LegacyGrantIndexes<'a> {
+    pub dao: MultiIndex<'a, Addr, Grant, String>,
+}
+
+impl<'a> IndexList<Grant> for LegacyGrantIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Grant>> + '_> {
+        let v: Vec<&dyn Index<Grant>> = vec![&self.dao];
+        Box::new(v.into_iter())
+    }
+}
+
+// Grants keyed by their id as string, only read when migrating to numeric keys
+pub fn legacy_grants<'a>() -> IndexedMap<'a, String, Grant, LegacyGrantIndexes<'a>> {
+    let indexes = LegacyGrantIndexes {
+        dao: MultiIndex::new(
+            |_pk: &[u8], d: &Grant| d.dao.clone(),
+            "grants",
+            "grants__dao",
+        ),
+    };
+    IndexedMap::new("grants", indexes)
+}
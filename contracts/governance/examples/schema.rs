@@ -21,4 +21,11 @@ fn main() {
     export_schema(&schema_for!(ProposalsResponse), &out_dir);
     export_schema(&schema_for!(ProposalMsg), &out_dir);
     export_schema(&schema_for!(CoreSlotsResponse), &out_dir);
+    export_schema(&schema_for!(SimulateProposalResponse), &out_dir);
+    export_schema(&schema_for!(VotesByVoterResponse), &out_dir);
+    export_schema(&schema_for!(VoterStatsResponse), &out_dir);
+    export_schema(&schema_for!(VotingRewardsResponse), &out_dir);
+    export_schema(&schema_for!(VotingRewardPoolResponse), &out_dir);
+    export_schema(&schema_for!(PauseInfoResponse), &out_dir);
+    export_schema(&schema_for!(FundingBudgetResponse), &out_dir);
 }
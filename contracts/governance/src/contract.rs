@@ -3,8 +3,12 @@ use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, ProposalMsg, QueryMsg};
 use crate::state::{Config, CoreSlots, CONFIG, CORE_SLOTS, PROPOSAL_COUNT};
 use artist_curator::msg::ExecuteMsg::ApproveCurator;
-use bjmes_token::msg::QueryMsg as BjmesQueryMsg;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use bjmes_token::msg::{
+    ExecuteMsg as BjmesExecuteMsg, QueryMsg as BjmesQueryMsg, VotingPowerResponse,
+};
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+};
 use cw2::set_contract_version;
 use identityservice::msg::QueryMsg::GetIdentityByOwner;
 use identityservice::state::IdType::Dao;
@@ -26,6 +30,29 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let owner_addr = deps.api.addr_validate(&msg.owner)?;
 
+    if msg.posting_period_length == 0 || msg.voting_period_length == 0 {
+        return Err(StdError::generic_err(
+            "posting_period_length and voting_period_length must be greater than 0",
+        ));
+    }
+
+    if msg.max_pause_duration == 0 {
+        return Err(StdError::generic_err(
+            "max_pause_duration must be greater than 0",
+        ));
+    }
+
+    if msg.voting_reward_percentage > 100 {
+        return Err(StdError::generic_err(
+            "voting_reward_percentage must not exceed 100",
+        ));
+    }
+
+    let guardian = msg
+        .guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+
     let config = Config {
         owner: Some(owner_addr),
         bjmes_token_addr: deps.api.addr_validate(&msg.bjmes_token_addr)?,
@@ -37,6 +64,10 @@ pub fn instantiate(
         period_start_epoch: msg.period_start_epoch,                     // 1660000000,
         posting_period_length: msg.posting_period_length,               // 300000,
         voting_period_length: msg.voting_period_length,                 // 606864,
+        voting_reward_percentage: msg.voting_reward_percentage,         // 10,
+        guardian,
+        max_pause_duration: msg.max_pause_duration, // 1209600,
+        funding_budget_per_cycle: msg.funding_budget_per_cycle,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -63,6 +94,28 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         Proposal { id } => to_binary(&query::proposal(deps, env, id)?),
         Proposals { start, limit } => to_binary(&query::proposals(deps, env, start, limit)?),
         CoreSlots {} => to_binary(&query::core_slots(deps, env)?),
+        SimulateProposal {
+            sender,
+            deposit,
+            proposal,
+        } => to_binary(&query::simulate_proposal(
+            deps, env, sender, deposit, proposal,
+        )?),
+        VotesByVoter {
+            voter,
+            start_after,
+            limit,
+        } => to_binary(&query::votes_by_voter(deps, voter, start_after, limit)?),
+        VoterStats {
+            voter,
+            start_after,
+            limit,
+        } => to_binary(&query::voter_stats(deps, voter, start_after, limit)?),
+        VotingRewards { voter } => to_binary(&query::voting_rewards(deps, env, voter)?),
+        VotingRewardPool { cycle } => to_binary(&query::voting_reward_pool(deps, cycle)?),
+        PauseInfo {} => to_binary(&query::pause_info(deps, env)?),
+        FundingBudget {} => to_binary(&query::funding_budget(deps, env)?),
+        OldestOpenProposal {} => to_binary(&query::oldest_open_proposal(deps, env)?),
     }
 }
 
@@ -81,6 +134,11 @@ pub fn execute(
         SetCoreSlot { proposal_id } => exec::set_core_slot(deps, env, info, proposal_id),
         UnsetCoreSlot { proposal_id } => exec::unset_core_slot(deps, env, info, proposal_id),
         ResignCoreSlot { slot, note } => exec::resign_core_slot(deps, env, info, slot, note),
+        ClaimVotingRewards { start_after, limit } => {
+            exec::claim_voting_rewards(deps, env, info, start_after, limit)
+        }
+        Pause {} => exec::pause(deps, env, info),
+        Unpause {} => exec::unpause(deps, env, info),
         SetContract {
             distribution,
             artist_curator,
@@ -96,22 +154,38 @@ pub fn execute(
     }
 }
 
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // Replies only come from grant proposal messages failing when concluding, keyed by proposal id
+    exec::proposal_msg_failed(deps, msg.id, msg.result)
+}
+
 mod exec {
-    use cosmwasm_std::{coins, BankMsg, CosmosMsg, Decimal, Uint128, WasmMsg};
-    use cw20::BalanceResponse;
+    use cosmwasm_std::{
+        coins, from_binary, Addr, BankMsg, CosmosMsg, Decimal, Order, Storage, SubMsg,
+        SubMsgResult, Timestamp, Uint128, WasmMsg,
+    };
+    use cw_storage_plus::Bound;
+    use distribution::msg::{
+        ExecuteMsg as DistributionExecuteMsg, QueryMsg as DistributionQueryMsg, TreasuryResponse,
+    };
+    use distribution::state::{Asset, VestingSchedule};
     use identityservice::msg::GetIdentityByOwnerResponse;
 
     use super::*;
 
     use crate::contract::query::period_info;
     use crate::msg::{
-        AddGrant, AddGrantMsg, CoreSlot, Feature, PeriodInfoResponse, ProposalPeriod,
-        RevokeCoreSlot,
+        AddGrant, AddGrantMsg, ApproveMilestone, ApproveMilestoneMsg, CoreSlot, Feature,
+        PeriodInfoResponse, ProposalMetadata, ProposalPeriod, RevokeCoreSlot, RevokeGrant,
+        RevokeGrantMsg,
     };
     use crate::state::{
-        Proposal, ProposalType,
+        active_pause, Proposal, ProposalType, RewardSettlement,
         VoteOption::{self, *},
-        PROPOSALS,
+        VoteRecord, CORE_SLOT_PAUSE_QUORUM, CYCLE_FUNDING, CYCLE_PROPOSAL_COUNT,
+        CYCLE_REWARDS_PAID, CYCLE_REWARD_SETTLEMENTS, CYCLE_VOTE_COINS, FUNDING_BOOKINGS,
+        PAUSED_UNTIL, PAUSE_REQUESTS, PROPOSALS, VOTER_CYCLE_STATS, VOTER_TOTALS, VOTES,
+        VOTING_REWARD_POOLS,
     };
     use crate::state::{ProposalStatus, SlotVoteResult, CORE_SLOTS};
 
@@ -121,6 +195,84 @@ mod exec {
         info: MessageInfo,
         proposal_msg: ProposalMsg,
     ) -> Result<Response, ContractError> {
+        let deposit_amount = info
+            .funds
+            .iter()
+            .find(|coin| coin.denom == "uluna")
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+
+        let id = Proposal::next_id(deps.storage)?;
+        let proposal = build_proposal(
+            deps.as_ref(),
+            &env,
+            &info.sender,
+            deposit_amount,
+            id,
+            proposal_msg,
+        )?;
+
+        PROPOSALS.save(deps.storage, id, &proposal)?;
+
+        // Count the proposals per cycle for the voter participation rate
+        let config = CONFIG.load(deps.storage)?;
+        CYCLE_PROPOSAL_COUNT.update(
+            deps.storage,
+            config.cycle(proposal.posting_start),
+            |count| -> StdResult<_> { Ok(count.unwrap_or_default() + 1) },
+        )?;
+
+        Ok(Response::new())
+    }
+
+    /// Fields shared by all proposal types, collected once the common checks passed
+    pub struct ProposalBase<'a> {
+        deps: Deps<'a>,
+        env: &'a Env,
+        config: Config,
+        period_info: PeriodInfoResponse,
+        dao: Addr,
+        deposit_amount: Uint128,
+        id: u64,
+        title: String,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+    }
+
+    impl ProposalBase<'_> {
+        fn into_proposal(self, prop_type: ProposalType, msgs: Option<Vec<CosmosMsg>>) -> Proposal {
+            Proposal {
+                id: self.id,
+                dao: self.dao,
+                title: self.title,
+                description: self.description,
+                metadata: self.metadata,
+                prop_type,
+                coins_no: Uint128::zero(),
+                coins_yes: Uint128::zero(),
+                yes_voters: Vec::new(),
+                no_voters: Vec::new(),
+                deposit_amount: self.deposit_amount,
+                start_block: self.env.block.height, // used for voting coin lookup
+                posting_start: self.period_info.current_posting_start,
+                voting_start: self.period_info.current_voting_start,
+                voting_end: self.period_info.current_voting_end,
+                concluded: false,
+                msgs,
+                execution_error: None,
+            }
+        }
+    }
+
+    /// Runs all checks for a proposal posted by `sender` and builds it, without saving it
+    pub fn build_proposal(
+        deps: Deps,
+        env: &Env,
+        sender: &Addr,
+        deposit_amount: Uint128,
+        id: u64,
+        proposal_msg: ProposalMsg,
+    ) -> Result<Proposal, ContractError> {
         let config = CONFIG.load(deps.storage)?;
 
         // Check if governance is already enabled (ensures fair distribution of coins for proposal deposit)
@@ -129,19 +281,18 @@ mod exec {
                 start_epoch: config.period_start_epoch,
             });
         }
-        let period_info = period_info(deps.as_ref(), env.clone())?;
-        let deposit_amount = info
-            .funds
-            .iter()
-            .find(|coin| coin.denom == "uluna")
-            .unwrap()
-            .amount;
+        let period_info = period_info(deps, env.clone())?;
+
+        // While paused only proposals to lift the pause can be posted
+        if !matches!(proposal_msg, ProposalMsg::Unpause { .. }) {
+            assert_not_paused(deps, env)?;
+        }
 
         // Only DAO identities are allowed to post proposals
         let maybe_identity_resp: GetIdentityByOwnerResponse = deps.querier.query_wasm_smart(
             config.clone().identityservice_addr.unwrap().clone(),
             &GetIdentityByOwner {
-                owner: info.sender.clone().into(),
+                owner: sender.to_string(),
             },
         )?;
 
@@ -161,150 +312,122 @@ mod exec {
             return Err(ContractError::InsufficientDeposit {});
         }
 
-        match proposal_msg {
-            ProposalMsg::TextProposal { title, description } => text_proposal(
-                deps,
-                info,
-                env,
-                config,
-                period_info,
-                deposit_amount,
+        let base = |title, description, metadata| ProposalBase {
+            deps,
+            env,
+            config,
+            period_info,
+            dao: sender.clone(),
+            deposit_amount,
+            id,
+            title,
+            description,
+            metadata,
+        };
+
+        let mut proposal = match proposal_msg {
+            ProposalMsg::TextProposal {
                 title,
                 description,
-            ),
+                metadata,
+            } => text_proposal(base(title, description, metadata)),
             ProposalMsg::RequestFeature {
                 title,
                 description,
+                metadata,
                 feature,
-            } => request_feature(
-                deps,
-                info,
-                env,
-                config,
-                period_info,
-                deposit_amount,
-                title,
-                description,
-                feature,
-            ),
+            } => request_feature(base(title, description, metadata), feature),
             ProposalMsg::Funding {
                 title,
                 description,
+                metadata,
                 duration,
                 amount,
+                vesting,
+                asset,
             } => funding(
-                deps,
-                info,
-                env,
-                config,
-                period_info,
-                deposit_amount,
-                title,
-                description,
+                base(title, description, metadata),
                 duration,
                 amount,
+                vesting,
+                asset,
             ),
             ProposalMsg::Improvement {
                 title,
                 description,
+                metadata,
                 msgs,
-            } => improvement(
-                deps,
-                info,
-                env,
-                config,
-                period_info,
-                deposit_amount,
-                title,
-                description,
-                msgs,
-            ),
+            } => improvement(base(title, description, metadata), msgs),
             ProposalMsg::CoreSlot {
                 title,
                 description,
+                metadata,
                 slot,
-            } => core_slot(
-                deps,
-                info,
-                env,
-                config,
-                period_info,
-                deposit_amount,
-                title,
-                description,
-                slot,
-            ),
+            } => core_slot(base(title, description, metadata), slot),
             ProposalMsg::RevokeCoreSlot {
                 title,
                 description,
+                metadata,
                 revoke_slot,
-            } => revoke_core_slot(
-                deps,
-                info,
-                env,
-                config,
-                period_info,
-                deposit_amount,
+            } => revoke_core_slot(base(title, description, metadata), revoke_slot),
+            ProposalMsg::RevokeGrant {
                 title,
                 description,
-                revoke_slot,
-            ),
-        }
-    }
-
-    pub fn text_proposal(
-        deps: DepsMut,
-        info: MessageInfo,
-        env: Env,
-        _config: Config,
-        period_info: PeriodInfoResponse,
-        deposit_amount: Uint128,
-        title: String,
-        description: String,
-    ) -> Result<Response, ContractError> {
-        let id = Proposal::next_id(deps.storage)?;
-        let proposal = Proposal {
-            id,
-            dao: info.sender,
-            title,
-            description,
-            prop_type: ProposalType::Text {},
-            coins_no: Uint128::zero(),
-            coins_yes: Uint128::zero(),
-            yes_voters: Vec::new(),
-            no_voters: Vec::new(),
-            deposit_amount,
-            start_block: env.block.height, // used for voting coin lookup
-            posting_start: period_info.current_posting_start,
-            voting_start: period_info.current_voting_start,
-            voting_end: period_info.current_voting_end,
-            concluded: false,
-            msgs: None,
-        };
+                metadata,
+                grant_id,
+            } => revoke_grant(base(title, description, metadata), grant_id),
+            ProposalMsg::PauseGrant {
+                title,
+                description,
+                metadata,
+                grant_id,
+            } => pause_grant(base(title, description, metadata), grant_id, true),
+            ProposalMsg::ResumeGrant {
+                title,
+                description,
+                metadata,
+                grant_id,
+            } => pause_grant(base(title, description, metadata), grant_id, false),
+            ProposalMsg::ApproveMilestone {
+                title,
+                description,
+                metadata,
+                grant_id,
+                milestone,
+            } => approve_milestone(base(title, description, metadata), grant_id, milestone),
+            ProposalMsg::TransferWhitelist {
+                title,
+                description,
+                metadata,
+                add,
+                remove,
+            } => transfer_whitelist(base(title, description, metadata), add, remove),
+            ProposalMsg::Unpause {
+                title,
+                description,
+                metadata,
+            } => unpause_proposal(base(title, description, metadata)),
+        }?;
 
+        proposal.normalize();
         proposal.validate()?;
 
-        PROPOSALS.save(deps.storage, id, &proposal)?;
+        Ok(proposal)
+    }
 
-        Ok(Response::new())
+    pub fn text_proposal(base: ProposalBase) -> Result<Proposal, ContractError> {
+        Ok(base.into_proposal(ProposalType::Text {}, None))
     }
 
     pub fn request_feature(
-        deps: DepsMut,
-        info: MessageInfo,
-        env: Env,
-        config: Config,
-        period_info: PeriodInfoResponse,
-        deposit_amount: Uint128,
-        title: String,
-        description: String,
+        base: ProposalBase,
         feature: Feature,
-    ) -> Result<Response, ContractError> {
+    ) -> Result<Proposal, ContractError> {
         let msg = match feature {
             Feature::ArtistCurator { approved, duration } => CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: config.artist_curator_addr.unwrap().to_string(),
+                contract_addr: base.config.artist_curator_addr.clone().unwrap().to_string(),
                 msg: to_binary(&ApproveCurator {
-                    dao: info.sender.clone(),
+                    dao: base.dao.clone(),
                     approved,
                     duration,
                 })?,
@@ -312,176 +435,172 @@ mod exec {
             }),
         };
 
-        let id = Proposal::next_id(deps.storage)?;
-        let proposal = Proposal {
-            id,
-            dao: info.sender,
-            title,
-            description,
-            prop_type: ProposalType::FeatureRequest(feature),
-            coins_no: Uint128::zero(),
-            coins_yes: Uint128::zero(),
-            yes_voters: Vec::new(),
-            no_voters: Vec::new(),
-            deposit_amount,
-            start_block: env.block.height, // used for voting coin lookup
-            posting_start: period_info.current_posting_start,
-            voting_start: period_info.current_voting_start,
-            voting_end: period_info.current_voting_end,
-            concluded: false,
-            msgs: Some(vec![msg]),
-        };
-
-        proposal.validate()?;
-
-        PROPOSALS.save(deps.storage, id, &proposal)?;
-
-        Ok(Response::new())
+        Ok(base.into_proposal(ProposalType::FeatureRequest(feature), Some(vec![msg])))
     }
 
     pub fn funding(
-        deps: DepsMut,
-        info: MessageInfo,
-        env: Env,
-        config: Config,
-        period_info: PeriodInfoResponse,
-        deposit_amount: Uint128,
-        title: String,
-        description: String,
+        base: ProposalBase,
         duration: u64,
         amount: Uint128,
-    ) -> Result<Response, ContractError> {
-        // Only the submitting dao address can receive the grant funding
-        let dao = info.sender.clone();
+        vesting: Option<VestingSchedule>,
+        asset: Option<Asset>,
+    ) -> Result<Proposal, ContractError> {
+        let deps = base.deps;
+
+        // Check the schedule now, a grant failing in the distribution contract can't be concluded
+        if let Some(vesting) = &vesting {
+            vesting
+                .validate(duration, amount)
+                .map_err(|err| ContractError::ProposalNotValid {
+                    error: err.to_string(),
+                })?;
+        }
+
+        // The budget is kept in uluna, grants in other assets would bypass it
+        let grant_asset = asset.clone().unwrap_or_default();
+        if base.config.funding_budget_per_cycle.is_some() && grant_asset != Asset::default() {
+            return Err(ContractError::ProposalNotValid {
+                error: format!(
+                    "Only {} can be granted while funding is limited per cycle",
+                    Asset::default()
+                ),
+            });
+        }
+
+        let distribution_addr = base.config.distribution_addr.clone().unwrap();
+
+        // The distribution contract rejects grants exceeding the free treasury balance
+        let treasury: TreasuryResponse = deps.querier.query_wasm_smart(
+            distribution_addr.clone(),
+            &DistributionQueryMsg::Treasury {},
+        )?;
+        let free = match treasury.assets.iter().find(|a| a.asset == grant_asset) {
+            Some(treasury_asset) => treasury_asset.free,
+            None => grant_asset.query_balance(&deps.querier, &distribution_addr)?,
+        };
+
+        if amount > free {
+            return Err(ContractError::ProposalNotValid {
+                error: format!("Only {} {} is free in the treasury", free, grant_asset),
+            });
+        }
 
+        // A grant over the budget would never fit into any cycle
+        if let Some(budget) = base.config.funding_budget_per_cycle {
+            if amount > budget {
+                return Err(ContractError::ProposalNotValid {
+                    error: format!("Funding is limited to {} {} per cycle", budget, grant_asset),
+                });
+            }
+        }
+
+        // Only the submitting dao address can receive the grant funding
         let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.distribution_addr.unwrap().to_string(),
+            contract_addr: distribution_addr.to_string(),
             msg: to_binary(&AddGrantMsg {
                 add_grant: AddGrant {
-                    dao: dao.clone(),
+                    dao: base.dao.clone(),
                     duration,
                     amount,
+                    vesting,
+                    asset,
+                    start: None,
                 },
             })?,
             funds: vec![],
         });
 
-        let id = Proposal::next_id(deps.storage)?;
-        let proposal = Proposal {
-            id,
-            dao,
-            title,
-            description,
-            prop_type: ProposalType::Funding {},
-            coins_no: Uint128::zero(),
-            coins_yes: Uint128::zero(),
-            yes_voters: Vec::new(),
-            no_voters: Vec::new(),
-            deposit_amount,
-            start_block: env.block.height, // used for voting coin lookup
-            posting_start: period_info.current_posting_start,
-            voting_start: period_info.current_voting_start,
-            voting_end: period_info.current_voting_end,
-            concluded: false,
-            msgs: Some(vec![msg]),
+        Ok(base.into_proposal(ProposalType::Funding {}, Some(vec![msg])))
+    }
+
+    pub fn revoke_grant(base: ProposalBase, grant_id: u64) -> Result<Proposal, ContractError> {
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: base.config.distribution_addr.clone().unwrap().to_string(),
+            msg: to_binary(&RevokeGrantMsg {
+                revoke_grant: RevokeGrant { grant_id },
+            })?,
+            funds: vec![],
+        });
+
+        Ok(base.into_proposal(ProposalType::RevokeGrant { grant_id }, Some(vec![msg])))
+    }
+
+    // Pauses the grant, or resumes it when `pause` is false
+    pub fn pause_grant(
+        base: ProposalBase,
+        grant_id: u64,
+        pause: bool,
+    ) -> Result<Proposal, ContractError> {
+        let (distribution_msg, prop_type) = if pause {
+            (
+                DistributionExecuteMsg::PauseGrant { grant_id },
+                ProposalType::PauseGrant { grant_id },
+            )
+        } else {
+            (
+                DistributionExecuteMsg::ResumeGrant { grant_id },
+                ProposalType::ResumeGrant { grant_id },
+            )
         };
 
-        proposal.validate()?;
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: base.config.distribution_addr.clone().unwrap().to_string(),
+            msg: to_binary(&distribution_msg)?,
+            funds: vec![],
+        });
 
-        PROPOSALS.save(deps.storage, id, &proposal)?;
+        Ok(base.into_proposal(prop_type, Some(vec![msg])))
+    }
 
-        Ok(Response::new())
+    pub fn approve_milestone(
+        base: ProposalBase,
+        grant_id: u64,
+        milestone: u32,
+    ) -> Result<Proposal, ContractError> {
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: base.config.distribution_addr.clone().unwrap().to_string(),
+            msg: to_binary(&ApproveMilestoneMsg {
+                approve_milestone: ApproveMilestone {
+                    grant_id,
+                    milestone,
+                },
+            })?,
+            funds: vec![],
+        });
+
+        Ok(base.into_proposal(
+            ProposalType::ApproveMilestone {
+                grant_id,
+                milestone,
+            },
+            Some(vec![msg]),
+        ))
     }
 
     pub fn improvement(
-        deps: DepsMut,
-        info: MessageInfo,
-        env: Env,
-        _config: Config,
-        period_info: PeriodInfoResponse,
-        deposit_amount: Uint128,
-        title: String,
-        description: String,
+        base: ProposalBase,
         msgs: Vec<CosmosMsg>,
-    ) -> Result<Response, ContractError> {
-        let core_slots = CORE_SLOTS.load(deps.storage)?;
+    ) -> Result<Proposal, ContractError> {
+        let core_slots = CORE_SLOTS.load(base.deps.storage)?;
 
         // Only the CoreSlot DAO can submit proposals
-        if core_slots.core_tech.map(|s| s.dao) != Some(info.sender.clone()) {
+        if core_slots.core_tech.map(|s| s.dao) != Some(base.dao.clone()) {
             return Err(ContractError::Unauthorized {});
         }
 
-        let core_tech_dao = info.sender.clone();
-
-        let id = Proposal::next_id(deps.storage)?;
-        let proposal = Proposal {
-            id,
-            dao: core_tech_dao.clone(),
-            title,
-            description,
-            prop_type: ProposalType::Improvement {},
-            coins_no: Uint128::zero(),
-            coins_yes: Uint128::zero(),
-            yes_voters: Vec::new(),
-            no_voters: Vec::new(),
-            deposit_amount,
-            start_block: env.block.height, // used for voting coin lookup
-            posting_start: period_info.current_posting_start,
-            voting_start: period_info.current_voting_start,
-            voting_end: period_info.current_voting_end,
-            concluded: false,
-            msgs: Some(msgs),
-        };
-
-        proposal.validate()?;
-
-        PROPOSALS.save(deps.storage, id, &proposal)?;
-
-        Ok(Response::new())
+        Ok(base.into_proposal(ProposalType::Improvement {}, Some(msgs)))
     }
 
-    pub fn core_slot(
-        deps: DepsMut,
-        info: MessageInfo,
-        env: Env,
-        _config: Config,
-        period_info: PeriodInfoResponse,
-        deposit_amount: Uint128,
-        title: String,
-        description: String,
-        slot: CoreSlot,
-    ) -> Result<Response, ContractError> {
-        let dao = info.sender.clone();
-
-        let id = Proposal::next_id(deps.storage)?;
-        let proposal = Proposal {
-            id,
-            dao: dao.clone(),
-            title,
-            description,
-            prop_type: ProposalType::CoreSlot(slot.clone()),
-            coins_no: Uint128::zero(),
-            coins_yes: Uint128::zero(),
-            yes_voters: Vec::new(),
-            no_voters: Vec::new(),
-            deposit_amount,
-            start_block: env.block.height, // used for voting coin lookup
-            posting_start: period_info.current_posting_start,
-            voting_start: period_info.current_voting_start,
-            voting_end: period_info.current_voting_end,
-            concluded: false,
-            msgs: Some(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: env.contract.address.to_string(),
-                msg: to_binary(&ExecuteMsg::SetCoreSlot { proposal_id: id })?,
-                funds: vec![],
-            })]),
-        };
-
-        proposal.validate()?;
-
-        PROPOSALS.save(deps.storage, id, &proposal)?;
+    pub fn core_slot(base: ProposalBase, slot: CoreSlot) -> Result<Proposal, ContractError> {
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: base.env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::SetCoreSlot {
+                proposal_id: base.id,
+            })?,
+            funds: vec![],
+        });
 
-        Ok(Response::new())
+        Ok(base.into_proposal(ProposalType::CoreSlot(slot), Some(vec![msg])))
     }
 
     pub fn vote(
@@ -502,6 +621,10 @@ mod exec {
 
             let mut proposal = PROPOSALS.load(deps.storage, id)?;
 
+            if !matches!(proposal.prop_type, ProposalType::Unpause {}) {
+                assert_not_paused(deps.as_ref(), &env)?;
+            }
+
             println!("\n\n proposal {:?}", proposal);
             if proposal.concluded {
                 return Err(ContractError::ProposalAlreadyConcluded {});
@@ -517,15 +640,16 @@ mod exec {
                 return Err(ContractError::UserAlreadyVoted {});
             }
 
-            let bjmes_amount: BalanceResponse = deps.querier.query_wasm_smart(
-                config.bjmes_token_addr,
-                &BjmesQueryMsg::BalanceAt {
+            // Balance plus the bonus for locked bJMES when the proposal was posted
+            let voting_power: VotingPowerResponse = deps.querier.query_wasm_smart(
+                config.bjmes_token_addr.clone(),
+                &BjmesQueryMsg::VotingPowerAt {
                     address: info.sender.to_string(),
                     block: proposal.start_block,
                 },
             )?;
 
-            let vote_coins = bjmes_amount.balance;
+            let vote_coins = voting_power.voting_power;
 
             if vote_coins.is_zero() {
                 return Err(ContractError::NoVoteCoins {});
@@ -544,6 +668,47 @@ mod exec {
 
             PROPOSALS.save(deps.storage, id, &proposal)?;
 
+            // Index the vote by voter for the participation history
+            let cycle = config.cycle(proposal.posting_start);
+
+            VOTES.save(
+                deps.storage,
+                (&info.sender, id),
+                &VoteRecord {
+                    proposal_id: id,
+                    vote: vote.clone(),
+                    coins: vote_coins,
+                    cycle,
+                },
+            )?;
+
+            VOTER_CYCLE_STATS.update(
+                deps.storage,
+                (&info.sender, cycle),
+                |stats| -> StdResult<_> {
+                    let mut stats = stats.unwrap_or_default();
+                    match vote {
+                        Yes => stats.yes_votes += 1,
+                        No => stats.no_votes += 1,
+                    }
+                    stats.vote_coins = stats.vote_coins.checked_add(vote_coins)?;
+                    Ok(stats)
+                },
+            )?;
+
+            VOTER_TOTALS.update(deps.storage, &info.sender, |totals| -> StdResult<_> {
+                let mut totals = totals.unwrap_or_default();
+                match vote {
+                    Yes => totals.yes_votes += 1,
+                    No => totals.no_votes += 1,
+                }
+                Ok(totals)
+            })?;
+
+            CYCLE_VOTE_COINS.update(deps.storage, cycle, |coins| -> StdResult<_> {
+                Ok(coins.unwrap_or_default().checked_add(vote_coins)?)
+            })?;
+
             Ok(Response::new())
         }
     }
@@ -557,6 +722,10 @@ mod exec {
             return Err(ContractError::VotingPeriodNotEnded {});
         }
 
+        if !matches!(proposal.prop_type, ProposalType::Unpause {}) {
+            assert_not_paused(deps.as_ref(), &env)?;
+        }
+
         if proposal.concluded {
             return Err(ContractError::ProposalAlreadyConcluded {});
         }
@@ -566,13 +735,42 @@ mod exec {
         PROPOSALS.save(deps.storage, id, &proposal)?;
 
         let mut msgs: Vec<CosmosMsg> = vec![];
+        let mut response = Response::new();
 
         // Only execute proposal msgs on success
-        if proposal.status(env, config.proposal_required_percentage)
+        if proposal.status(env.clone(), config.proposal_required_percentage)
             == ProposalStatus::SuccessConcluded
             && proposal.msgs.is_some()
         {
-            msgs.extend(proposal.msgs.unwrap());
+            let proposal_msgs = proposal.msgs.unwrap();
+
+            if matches!(proposal.prop_type, ProposalType::Funding {}) {
+                // The treasury may not hold enough anymore to add the grant, this must not
+                // prevent concluding the proposal
+                for msg in proposal_msgs {
+                    let (msg, funding_cycle) = book_funding(deps.storage, &env, &config, id, msg)?;
+                    if let Some(funding_cycle) = funding_cycle {
+                        response =
+                            response.add_attribute("funding_cycle", funding_cycle.to_string());
+                    }
+                    response = response.add_submessage(SubMsg::reply_on_error(msg, id));
+                }
+            } else if matches!(
+                proposal.prop_type,
+                ProposalType::RevokeGrant { .. }
+                    | ProposalType::PauseGrant { .. }
+                    | ProposalType::ResumeGrant { .. }
+                    | ProposalType::ApproveMilestone { .. }
+            ) {
+                // The grant may have changed since the proposal was posted, e.g. by another
+                // proposal of the same cycle, or a milestone may still lack its report. This must
+                // not prevent concluding the proposal.
+                for msg in proposal_msgs {
+                    response = response.add_submessage(SubMsg::reply_on_error(msg, id));
+                }
+            } else {
+                msgs.extend(proposal_msgs);
+            }
 
             // Refund the proposal deposit
             msgs.push(CosmosMsg::Bank(BankMsg::Send {
@@ -580,14 +778,128 @@ mod exec {
                 amount: coins(proposal.deposit_amount.u128(), "uluna"),
             }));
         } else {
-            // Forward the proposal deposit to the distribution contract
-            msgs.push(CosmosMsg::Bank(BankMsg::Send {
-                to_address: config.distribution_addr.unwrap().to_string(),
-                amount: coins(proposal.deposit_amount.u128(), "uluna"),
-            }));
+            // Keep a share of the forfeited deposit for the voters of the proposal's cycle,
+            // unless nobody voted in that cycle to claim it
+            let cycle = config.cycle(proposal.posting_start);
+            let voting_reward = if CYCLE_VOTE_COINS
+                .may_load(deps.storage, cycle)?
+                .unwrap_or_default()
+                .is_zero()
+            {
+                Uint128::zero()
+            } else {
+                proposal
+                    .deposit_amount
+                    .multiply_ratio(config.voting_reward_percentage, 100u64)
+            };
+
+            if !voting_reward.is_zero() {
+                VOTING_REWARD_POOLS.update(deps.storage, cycle, |pool| -> StdResult<_> {
+                    Ok(pool.unwrap_or_default().checked_add(voting_reward)?)
+                })?;
+            }
+
+            // Forward the rest of the proposal deposit to the distribution contract
+            let forwarded_amount = proposal.deposit_amount.checked_sub(voting_reward)?;
+
+            if !forwarded_amount.is_zero() {
+                msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: config.distribution_addr.unwrap().to_string(),
+                    amount: coins(forwarded_amount.u128(), "uluna"),
+                }));
+            }
         }
 
-        Ok(Response::new().add_messages(msgs))
+        Ok(response.add_messages(msgs))
+    }
+
+    // Books an uluna grant into the first cycle with enough budget left, starting from the
+    // current one. Grants queued to a later cycle start vesting when that cycle starts.
+    fn book_funding(
+        storage: &mut dyn Storage,
+        env: &Env,
+        config: &Config,
+        id: u64,
+        msg: CosmosMsg,
+    ) -> Result<(CosmosMsg, Option<u64>), ContractError> {
+        let budget = match config.funding_budget_per_cycle {
+            Some(budget) => budget,
+            None => return Ok((msg, None)),
+        };
+
+        let (contract_addr, mut add_grant_msg) = match &msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg: add_grant_msg,
+                ..
+            }) => (
+                contract_addr.clone(),
+                from_binary::<AddGrantMsg>(add_grant_msg)?,
+            ),
+            _ => return Ok((msg, None)),
+        };
+
+        // Grants in other assets can't be proposed while a budget is set
+        let add_grant = &mut add_grant_msg.add_grant;
+        if add_grant.asset.clone().unwrap_or_default() != Asset::default() {
+            return Ok((msg, None));
+        }
+
+        let current_cycle = config.cycle(env.block.time.seconds());
+        let mut cycle = current_cycle;
+        loop {
+            let spent = CYCLE_FUNDING
+                .may_load(storage, cycle)?
+                .unwrap_or_default()
+                .checked_add(add_grant.amount)
+                .map_err(StdError::from)?;
+            if spent <= budget {
+                CYCLE_FUNDING.save(storage, cycle, &spent)?;
+                break;
+            }
+            cycle += 1;
+        }
+
+        FUNDING_BOOKINGS.save(storage, id, &(cycle, add_grant.amount))?;
+
+        if cycle > current_cycle {
+            add_grant.start = Some(Timestamp::from_seconds(config.cycle_start(cycle)));
+        }
+
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&add_grant_msg)?,
+            funds: vec![],
+        });
+
+        Ok((msg, Some(cycle)))
+    }
+
+    // Records why the concluded proposal couldn't be executed and releases its booked budget
+    pub fn proposal_msg_failed(
+        deps: DepsMut,
+        id: u64,
+        result: SubMsgResult,
+    ) -> Result<Response, ContractError> {
+        let error = match result {
+            SubMsgResult::Err(error) => error,
+            SubMsgResult::Ok(_) => return Ok(Response::new()),
+        };
+
+        let mut proposal = PROPOSALS.load(deps.storage, id)?;
+        proposal.execution_error = Some(error);
+        PROPOSALS.save(deps.storage, id, &proposal)?;
+
+        if let Some((cycle, amount)) = FUNDING_BOOKINGS.may_load(deps.storage, id)? {
+            CYCLE_FUNDING.update(deps.storage, cycle, |spent| -> StdResult<_> {
+                Ok(spent.unwrap_or_default().checked_sub(amount)?)
+            })?;
+            FUNDING_BOOKINGS.remove(deps.storage, id);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "proposal_msg_failed")
+            .add_attribute("proposal_id", id.to_string()))
     }
 
     pub fn resign_core_slot(
@@ -629,50 +941,227 @@ mod exec {
             .add_attribute("note", note))
     }
 
-    pub fn revoke_core_slot(
+    // Pays out the voter's share of the reward pools of all ended cycles
+    pub fn claim_voting_rewards(
         deps: DepsMut,
-        info: MessageInfo,
         env: Env,
-        _config: Config,
-        period_info: PeriodInfoResponse,
-        deposit_amount: Uint128,
-        title: String,
-        description: String,
-        revoke_slot: RevokeCoreSlot,
+        info: MessageInfo,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     ) -> Result<Response, ContractError> {
-        let dao = info.sender.clone();
+        let config = CONFIG.load(deps.storage)?;
 
-        let id = Proposal::next_id(deps.storage)?;
-        let proposal = Proposal {
-            id,
-            dao: dao.clone(),
-            title,
-            description,
-            prop_type: ProposalType::RevokeCoreSlot(revoke_slot),
-            coins_no: Uint128::zero(),
-            coins_yes: Uint128::zero(),
-            yes_voters: Vec::new(),
-            no_voters: Vec::new(),
-            deposit_amount,
-            start_block: env.block.height, // used for voting coin lookup
-            posting_start: period_info.current_posting_start,
-            voting_start: period_info.current_voting_start,
-            voting_end: period_info.current_voting_end,
-            concluded: false,
-            msgs: Some(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: env.contract.address.to_string(),
-                msg: to_binary(&ExecuteMsg::UnsetCoreSlot { proposal_id: id })?,
-                funds: vec![],
-            })]),
-        };
+        // Votes and forfeited deposits of the current cycle are not final yet
+        let current_cycle = config.cycle(env.block.time.seconds());
 
-        println!("\n\nproposal {:?}", proposal);
+        // Late conclusions can still grow the pool of a past cycle, so voters page through
+        // their cycles instead of keeping a cursor
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let voter_cycles = VOTER_CYCLE_STATS
+            .prefix(&info.sender)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                Some(Bound::exclusive(current_cycle)),
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        let last_cycle = voter_cycles.last().map(|(cycle, _)| *cycle);
+
+        let mut claimed_amount = Uint128::zero();
+        let mut dust_amount = Uint128::zero();
+        let mut settled = false;
+
+        for (cycle, mut stats) in voter_cycles {
+            let pool = VOTING_REWARD_POOLS
+                .may_load(deps.storage, cycle)?
+                .unwrap_or_default();
+            if stats.settled_pool == pool {
+                continue;
+            }
+            let total_vote_coins = CYCLE_VOTE_COINS
+                .may_load(deps.storage, cycle)?
+                .unwrap_or_default();
+            let mut paid = CYCLE_REWARDS_PAID
+                .may_load(deps.storage, cycle)?
+                .unwrap_or_default();
+
+            let claimable_amount = stats.claimable_rewards(pool, total_vote_coins, paid);
+            stats.rewards_claimed = stats.rewards_claimed.checked_add(claimable_amount)?;
+            stats.settled_pool = pool;
+            VOTER_CYCLE_STATS.save(deps.storage, (&info.sender, cycle), &stats)?;
+            paid = paid.checked_add(claimable_amount)?;
+
+            // Once all voters claimed their share of the pool only rounding dust is left
+            let mut settlement = CYCLE_REWARD_SETTLEMENTS
+                .may_load(deps.storage, cycle)?
+                .filter(|settlement| settlement.pool == pool)
+                .unwrap_or(RewardSettlement {
+                    pool,
+                    vote_coins: Uint128::zero(),
+                });
+            settlement.vote_coins = settlement.vote_coins.checked_add(stats.vote_coins)?;
+            if settlement.vote_coins == total_vote_coins {
+                dust_amount = dust_amount.checked_add(pool.checked_sub(paid)?)?;
+                paid = pool;
+            }
+            CYCLE_REWARD_SETTLEMENTS.save(deps.storage, cycle, &settlement)?;
+            CYCLE_REWARDS_PAID.save(deps.storage, cycle, &paid)?;
 
-        proposal.validate()?;
+            claimed_amount = claimed_amount.checked_add(claimable_amount)?;
+            settled = true;
+        }
 
-        PROPOSALS.save(deps.storage, id, &proposal)?;
+        if !settled {
+            return Err(ContractError::NoVotingRewards {});
+        }
 
-        Ok(Response::new())
+        let mut response = Response::new();
+        if !claimed_amount.is_zero() {
+            response = response.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(claimed_amount.u128(), "uluna"),
+            });
+        }
+        if let (false, Some(distribution_addr)) = (dust_amount.is_zero(), config.distribution_addr)
+        {
+            response = response.add_message(BankMsg::Send {
+                to_address: distribution_addr.to_string(),
+                amount: coins(dust_amount.u128(), "uluna"),
+            });
+        }
+
+        Ok(response
+            .add_attribute("action", "claim_voting_rewards")
+            .add_attribute("voter", info.sender.to_string())
+            .add_attribute("claimed_amount", claimed_amount)
+            .add_attribute("last_cycle", last_cycle.unwrap_or_default().to_string()))
+    }
+
+    pub fn revoke_core_slot(
+        base: ProposalBase,
+        revoke_slot: RevokeCoreSlot,
+    ) -> Result<Proposal, ContractError> {
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: base.env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::UnsetCoreSlot {
+                proposal_id: base.id,
+            })?,
+            funds: vec![],
+        });
+
+        Ok(base.into_proposal(ProposalType::RevokeCoreSlot(revoke_slot), Some(vec![msg])))
+    }
+
+    pub fn transfer_whitelist(
+        base: ProposalBase,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Proposal, ContractError> {
+        // Fail on invalid addresses now rather than when concluding
+        for contract in add.iter().chain(remove.iter()) {
+            base.deps.api.addr_validate(contract)?;
+        }
+
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: base.config.bjmes_token_addr.to_string(),
+            msg: to_binary(&BjmesExecuteMsg::UpdateTransferWhitelist { add, remove })?,
+            funds: vec![],
+        });
+
+        Ok(base.into_proposal(ProposalType::TransferWhitelist {}, Some(vec![msg])))
+    }
+
+    pub fn unpause_proposal(base: ProposalBase) -> Result<Proposal, ContractError> {
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: base.env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::Unpause {})?,
+            funds: vec![],
+        });
+
+        Ok(base.into_proposal(ProposalType::Unpause {}, Some(vec![msg])))
+    }
+
+    fn assert_not_paused(deps: Deps, env: &Env) -> Result<(), ContractError> {
+        match active_pause(deps.storage, env)? {
+            Some(paused_until) => Err(ContractError::Paused { paused_until }),
+            None => Ok(()),
+        }
+    }
+
+    // The guardian pauses right away, core slot DAOs need a quorum of recent requests
+    pub fn pause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+
+        if active_pause(deps.storage, &env)?.is_some() {
+            return Err(ContractError::AlreadyPaused {});
+        }
+
+        let now = env.block.time.seconds();
+
+        // Governance stays unpaused at least as long as the maximum pause after each pause
+        if let Some(paused_until) = PAUSED_UNTIL.may_load(deps.storage)? {
+            let available_at = paused_until + config.max_pause_duration;
+            if now < available_at {
+                return Err(ContractError::PauseCooldown { available_at });
+            }
+        }
+
+        if config.guardian.as_ref() != Some(&info.sender) {
+            let core_slot_daos = CORE_SLOTS.load(deps.storage)?.daos();
+
+            if !core_slot_daos.contains(&info.sender) {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            PAUSE_REQUESTS.save(deps.storage, &info.sender, &now)?;
+
+            // Requests older than the maximum pause duration have lapsed
+            let mut pause_requests = 0;
+            for dao in core_slot_daos.iter() {
+                if let Some(requested_at) = PAUSE_REQUESTS.may_load(deps.storage, dao)? {
+                    if requested_at + config.max_pause_duration > now {
+                        pause_requests += 1;
+                    }
+                }
+            }
+
+            if pause_requests < CORE_SLOT_PAUSE_QUORUM {
+                return Ok(Response::new()
+                    .add_attribute("action", "request_pause")
+                    .add_attribute("dao", info.sender.to_string())
+                    .add_attribute("pause_requests", pause_requests.to_string()));
+            }
+
+            for dao in core_slot_daos.iter() {
+                PAUSE_REQUESTS.remove(deps.storage, dao);
+            }
+        }
+
+        let paused_until = now + config.max_pause_duration;
+        PAUSED_UNTIL.save(deps.storage, &paused_until)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "pause")
+            .add_attribute("sender", info.sender.to_string())
+            .add_attribute("paused_until", paused_until.to_string()))
+    }
+
+    pub fn unpause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        // Only the governance contract itself can lift the pause
+        if info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        // Don't fail if the pause already expired, so the proposal still concludes. The end of
+        // the pause is kept for the cooldown before the next one
+        let now = env.block.time.seconds();
+        if let Some(paused_until) = PAUSED_UNTIL.may_load(deps.storage)? {
+            PAUSED_UNTIL.save(deps.storage, &paused_until.min(now))?;
+        }
+
+        Ok(Response::new().add_attribute("action", "unpause"))
     }
 
     pub fn unset_core_slot(
@@ -886,10 +1375,20 @@ mod query {
     use cosmwasm_std::Order;
     use cw_storage_plus::Bound;
 
+    use cosmwasm_std::{Decimal, Uint128};
+
+    use crate::contract::exec::build_proposal;
     use crate::msg::{
-        CoreSlotsResponse, PeriodInfoResponse, ProposalPeriod, ProposalResponse, ProposalsResponse,
+        CoreSlotsResponse, FundingBudgetResponse, OldestOpenProposalResponse, PauseInfoResponse,
+        PeriodInfoResponse, ProposalPeriod, ProposalResponse, ProposalsResponse,
+        SimulateProposalResponse, VoterCycleStatsResponse, VoterStatsResponse,
+        VotesByVoterResponse, VotingRewardPoolResponse, VotingRewardsResponse,
+    };
+    use crate::state::{
+        active_pause, Proposal, CYCLE_FUNDING, CYCLE_PROPOSAL_COUNT, CYCLE_REWARDS_PAID,
+        CYCLE_VOTE_COINS, PAUSE_REQUESTS, PROPOSALS, PROPOSAL_COUNT, VOTER_CYCLE_STATS,
+        VOTER_TOTALS, VOTES, VOTING_REWARD_POOLS,
     };
-    use crate::state::{PROPOSALS, PROPOSAL_COUNT};
 
     use super::*;
 
@@ -944,28 +1443,35 @@ mod query {
         })
     }
 
-    pub fn proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
-        let proposal = PROPOSALS.load(deps.storage, id)?;
-        let config = CONFIG.load(deps.storage)?;
-
-        Ok(ProposalResponse {
+    fn proposal_response(proposal: Proposal, env: &Env, config: &Config) -> ProposalResponse {
+        let status = proposal.status(env.clone(), config.proposal_required_percentage);
+        ProposalResponse {
             id: proposal.id,
-            dao: proposal.dao.clone(),
-            title: proposal.title.clone(),
-            description: proposal.description.clone(),
-            prop_type: proposal.prop_type.clone(),
+            dao: proposal.dao,
+            title: proposal.title,
+            description: proposal.description,
+            metadata: proposal.metadata,
+            prop_type: proposal.prop_type,
             coins_yes: proposal.coins_yes,
             coins_no: proposal.coins_no,
-            yes_voters: proposal.yes_voters.clone(),
-            no_voters: proposal.no_voters.clone(),
+            yes_voters: proposal.yes_voters,
+            no_voters: proposal.no_voters,
             deposit_amount: proposal.deposit_amount,
             start_block: proposal.start_block,
             posting_start: proposal.posting_start,
             voting_start: proposal.voting_start,
             voting_end: proposal.voting_end,
             concluded: proposal.concluded,
-            status: proposal.status(env.clone(), config.proposal_required_percentage),
-        })
+            status,
+            execution_error: proposal.execution_error,
+        }
+    }
+
+    pub fn proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
+        let proposal = PROPOSALS.load(deps.storage, id)?;
+        let config = CONFIG.load(deps.storage)?;
+
+        Ok(proposal_response(proposal, &env, &config))
     }
 
     pub fn proposals(
@@ -985,24 +1491,7 @@ mod query {
             .take(limit)
             .map(|item| {
                 let (_, proposal) = item?;
-                Ok(ProposalResponse {
-                    id: proposal.id,
-                    dao: proposal.dao.clone(),
-                    title: proposal.title.clone(),
-                    description: proposal.description.clone(),
-                    prop_type: proposal.prop_type.clone(),
-                    coins_yes: proposal.coins_yes,
-                    coins_no: proposal.coins_no,
-                    yes_voters: proposal.yes_voters.clone(),
-                    no_voters: proposal.no_voters.clone(),
-                    deposit_amount: proposal.deposit_amount,
-                    start_block: proposal.start_block,
-                    posting_start: proposal.posting_start,
-                    voting_start: proposal.voting_start,
-                    voting_end: proposal.voting_end,
-                    concluded: proposal.concluded,
-                    status: proposal.status(env.clone(), config.proposal_required_percentage),
-                })
+                Ok(proposal_response(proposal, &env, &config))
             })
             .collect::<StdResult<Vec<_>>>()?;
 
@@ -1011,6 +1500,233 @@ mod query {
             proposals,
         })
     }
+
+    pub fn simulate_proposal(
+        deps: Deps,
+        env: Env,
+        sender: String,
+        deposit: Uint128,
+        proposal_msg: ProposalMsg,
+    ) -> StdResult<SimulateProposalResponse> {
+        let sender = deps.api.addr_validate(&sender)?;
+        let config = CONFIG.load(deps.storage)?;
+
+        // The proposal would be posted with the next id
+        let id = PROPOSAL_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+
+        let proposal = build_proposal(deps, &env, &sender, deposit, id, proposal_msg).map_err(
+            |err| match err {
+                ContractError::StdError(err) => err,
+                err => StdError::generic_err(err.to_string()),
+            },
+        )?;
+        let msgs = proposal.msgs.clone().unwrap_or_default();
+
+        Ok(SimulateProposalResponse {
+            proposal: proposal_response(proposal, &env, &config),
+            msgs,
+        })
+    }
+
+    pub fn votes_by_voter(
+        deps: Deps,
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<VotesByVoterResponse> {
+        let voter = deps.api.addr_validate(&voter)?;
+
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let votes = VOTES
+            .prefix(&voter)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, vote)| vote))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(VotesByVoterResponse { votes })
+    }
+
+    pub fn voter_stats(
+        deps: Deps,
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<VoterStatsResponse> {
+        let voter = deps.api.addr_validate(&voter)?;
+
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        // Every cycle with proposals counts towards the participation rate
+        let cycles = CYCLE_PROPOSAL_COUNT
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (cycle, cycle_proposals) = item?;
+                let stats = VOTER_CYCLE_STATS
+                    .may_load(deps.storage, (&voter, cycle))?
+                    .unwrap_or_default();
+                let votes = stats.yes_votes + stats.no_votes;
+
+                Ok(VoterCycleStatsResponse {
+                    cycle,
+                    votes,
+                    yes_votes: stats.yes_votes,
+                    no_votes: stats.no_votes,
+                    proposals: cycle_proposals,
+                    participation_rate: Decimal::from_ratio(votes, cycle_proposals),
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let totals = VOTER_TOTALS
+            .may_load(deps.storage, &voter)?
+            .unwrap_or_default();
+        let votes = totals.yes_votes + totals.no_votes;
+        let proposals = PROPOSAL_COUNT.load(deps.storage)?;
+        let participation_rate = if proposals == 0 {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(votes, proposals)
+        };
+
+        Ok(VoterStatsResponse {
+            voter,
+            votes,
+            yes_votes: totals.yes_votes,
+            no_votes: totals.no_votes,
+            participation_rate,
+            cycles,
+        })
+    }
+
+    pub fn voting_rewards(deps: Deps, env: Env, voter: String) -> StdResult<VotingRewardsResponse> {
+        let voter = deps.api.addr_validate(&voter)?;
+        let config = CONFIG.load(deps.storage)?;
+
+        let current_cycle = config.cycle(env.block.time.seconds());
+
+        let claimable_amount = VOTER_CYCLE_STATS
+            .prefix(&voter)
+            .range(
+                deps.storage,
+                None,
+                Some(Bound::exclusive(current_cycle)),
+                Order::Ascending,
+            )
+            .try_fold(Uint128::zero(), |claimable_amount, item| {
+                let (cycle, stats) = item?;
+                let pool = VOTING_REWARD_POOLS
+                    .may_load(deps.storage, cycle)?
+                    .unwrap_or_default();
+                let total_vote_coins = CYCLE_VOTE_COINS
+                    .may_load(deps.storage, cycle)?
+                    .unwrap_or_default();
+                let paid = CYCLE_REWARDS_PAID
+                    .may_load(deps.storage, cycle)?
+                    .unwrap_or_default();
+                Ok::<_, StdError>(claimable_amount.checked_add(stats.claimable_rewards(
+                    pool,
+                    total_vote_coins,
+                    paid,
+                ))?)
+            })?;
+
+        Ok(VotingRewardsResponse {
+            voter,
+            claimable_amount,
+        })
+    }
+
+    pub fn voting_reward_pool(deps: Deps, cycle: u64) -> StdResult<VotingRewardPoolResponse> {
+        Ok(VotingRewardPoolResponse {
+            cycle,
+            amount: VOTING_REWARD_POOLS
+                .may_load(deps.storage, cycle)?
+                .unwrap_or_default(),
+            total_vote_coins: CYCLE_VOTE_COINS
+                .may_load(deps.storage, cycle)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn funding_budget(deps: Deps, env: Env) -> StdResult<FundingBudgetResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let cycle = config.cycle(env.block.time.seconds());
+
+        let spent = CYCLE_FUNDING
+            .may_load(deps.storage, cycle)?
+            .unwrap_or_default();
+
+        let queued = CYCLE_FUNDING
+            .range(
+                deps.storage,
+                Some(Bound::exclusive(cycle)),
+                None,
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(_, amount)| amount))
+            .sum::<StdResult<Uint128>>()?;
+
+        Ok(FundingBudgetResponse {
+            cycle,
+            budget: config.funding_budget_per_cycle,
+            spent,
+            remaining: config
+                .funding_budget_per_cycle
+                .map(|budget| budget.saturating_sub(spent)),
+            queued,
+        })
+    }
+
+    pub fn oldest_open_proposal(deps: Deps, env: Env) -> StdResult<OldestOpenProposalResponse> {
+        let now = env.block.time.seconds();
+
+        // Later proposals never end their voting earlier, so walk back from the newest one
+        // until voting has ended
+        let mut oldest = None;
+        for item in PROPOSALS.range(deps.storage, None, None, Order::Descending) {
+            let (_, proposal) = item?;
+            if proposal.voting_end < now {
+                break;
+            }
+            if !proposal.concluded {
+                oldest = Some(proposal);
+            }
+        }
+
+        Ok(OldestOpenProposalResponse {
+            id: oldest.as_ref().map(|proposal| proposal.id),
+            start_block: oldest.map(|proposal| proposal.start_block),
+        })
+    }
+
+    pub fn pause_info(deps: Deps, env: Env) -> StdResult<PauseInfoResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let paused_until = active_pause(deps.storage, &env)?;
+        let now = env.block.time.seconds();
+
+        let pause_requests = PAUSE_REQUESTS
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter_map(|item| match item {
+                Ok((dao, requested_at)) if requested_at + config.max_pause_duration > now => {
+                    Some(Ok(dao))
+                }
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(PauseInfoResponse {
+            paused: paused_until.is_some(),
+            paused_until,
+            guardian: config.guardian,
+            pause_requests,
+        })
+    }
 }
 
 // #[cfg(test)]
@@ -1034,7 +1750,7 @@ mod query {
 //     use bjmes_token::contract::query as bjmes_query;
 //     use bjmes_token::msg::ExecuteMsg as BjmesExecuteMsg;
 //     use bjmes_token::msg::InstantiateMsg as BjmesInstantiateMsg;
-//     use bjmes_token::msg::QueryMsg as BjmesQueryMsg;
+//     use bjmes_token::msg::{QueryMsg as BjmesQueryMsg, VotingPowerResponse};
 
 //     // use artist_curator::contract::execute as artist_curator_execute;
 //     // use artist_curator::contract::instantiate as artist_curator_instantiate;
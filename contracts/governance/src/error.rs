@@ -35,6 +35,14 @@ pub enum ContractError {
     WrongDao {},
     #[error("AlreadyHoldingCoreSlot")]
     AlreadyHoldingCoreSlot {},
+    #[error("NoVotingRewards")]
+    NoVotingRewards {},
+    #[error("Paused: Governance is paused until {paused_until}")]
+    Paused { paused_until: u64 },
+    #[error("AlreadyPaused")]
+    AlreadyPaused {},
+    #[error("PauseCooldown: Governance can't be paused again before {available_at}")]
+    PauseCooldown { available_at: u64 },
 }
 
 impl From<OverflowError> for ContractError {
@@ -1,10 +1,11 @@
 use std::fmt;
 
-use cosmwasm_std::{Addr, CosmosMsg, Uint128};
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Timestamp, Uint128};
+use distribution::state::{Asset, VestingSchedule};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::{ProposalStatus, ProposalType, SlotVoteResult, VoteOption};
+use crate::state::{ProposalStatus, ProposalType, SlotVoteResult, VoteOption, VoteRecord};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -21,6 +22,14 @@ pub struct InstantiateMsg {
     pub posting_period_length: u64,
     // Length in seconds of the posting period, e.g.  606864 for ~ 1 Week (year/52)
     pub voting_period_length: u64,
+    // Percentage of forfeited proposal deposits paid out to the voters of the cycle, e.g. 10
+    pub voting_reward_percentage: u64,
+    // Address that can pause governance in an emergency
+    pub guardian: Option<String>,
+    // Length in seconds after which a pause is lifted automatically
+    pub max_pause_duration: u64,
+    // Max total uluna granted by funding proposals concluded in a cycle, unlimited if not set
+    pub funding_budget_per_cycle: Option<Uint128>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
@@ -50,6 +59,16 @@ pub enum ExecuteMsg {
         slot: CoreSlot,
         note: String, // Can be used to explain why the dao is resigning, is only added as an attribute to the events
     },
+    /// Claims the voting rewards of up to `limit` past cycles the sender voted in, after
+    /// `start_after`
+    ClaimVotingRewards {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Pauses Propose, Vote and Conclude, callable by the guardian or a quorum of core slot DAOs
+    Pause {},
+    /// Lifts the pause, only callable by the governance contract via an Unpause proposal
+    Unpause {},
     // RemoveFeature { feature: Feature },
 
     // RequestCoreSlot { core_slot: CoreSlot },
@@ -66,34 +85,94 @@ pub enum ProposalMsg {
     TextProposal {
         title: String,
         description: String,
+        metadata: Option<ProposalMetadata>,
     },
     RequestFeature {
         title: String,
         description: String,
+        metadata: Option<ProposalMetadata>,
         feature: Feature,
     },
     Funding {
         title: String,
         description: String,
+        metadata: Option<ProposalMetadata>,
         duration: u64,
         amount: Uint128,
+        // Defaults to linear vesting
+        vesting: Option<VestingSchedule>,
+        // Defaults to uluna
+        asset: Option<Asset>,
     },
     Improvement {
         title: String,
         description: String,
+        metadata: Option<ProposalMetadata>,
         msgs: Vec<CosmosMsg>,
     },
     CoreSlot {
         title: String,
         description: String,
+        metadata: Option<ProposalMetadata>,
         slot: CoreSlot,
     },
     RevokeCoreSlot {
         title: String,
         description: String,
+        metadata: Option<ProposalMetadata>,
         revoke_slot: RevokeCoreSlot,
     },
+    RevokeGrant {
+        title: String,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+        grant_id: u64,
+    },
+    // Freezes a grant while the dao is under investigation
+    PauseGrant {
+        title: String,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+        grant_id: u64,
+    },
+    ResumeGrant {
+        title: String,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+        grant_id: u64,
+    },
+    ApproveMilestone {
+        title: String,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+        grant_id: u64,
+        milestone: u32,
+    },
+    // Adds and removes contracts allowed to send or receive bJMES
+    TransferWhitelist {
+        title: String,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // The only proposal type that can be posted, voted on and concluded while paused
+    Unpause {
+        title: String,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+    },
 }
+/// Off-chain proposal specification, e.g. a document pinned on IPFS
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalMetadata {
+    // Location of the document, must start with ipfs:// or https://
+    pub uri: String,
+    // Hex encoded sha256 hash of the document content
+    pub content_hash: String,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct AddGrantMsg {
@@ -105,6 +184,32 @@ pub struct AddGrant {
     pub dao: Addr,
     pub duration: u64,
     pub amount: Uint128,
+    pub vesting: Option<VestingSchedule>,
+    pub asset: Option<Asset>,
+    pub start: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RevokeGrantMsg {
+    pub revoke_grant: RevokeGrant,
+}
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RevokeGrant {
+    pub grant_id: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ApproveMilestoneMsg {
+    pub approve_milestone: ApproveMilestone,
+}
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ApproveMilestone {
+    pub grant_id: u64,
+    pub milestone: u32,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
@@ -151,6 +256,35 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     CoreSlots {},
+    /// Runs all checks of a proposal posted by `sender` with a `deposit` (in uluna) and returns
+    /// the proposal with the msgs it would execute, without posting it
+    SimulateProposal {
+        sender: String,
+        deposit: Uint128,
+        proposal: ProposalMsg,
+    },
+    VotesByVoter {
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Totals over all cycles and the stats of up to `limit` cycles after `start_after`
+    VoterStats {
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    VotingRewards {
+        voter: String,
+    },
+    VotingRewardPool {
+        cycle: u64,
+    },
+    PauseInfo {},
+    FundingBudget {},
+    /// The proposal still open for votes that was posted at the lowest block, if any. Its
+    /// `start_block` is the oldest block bJMES balances may still be looked up at
+    OldestOpenProposal {},
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
@@ -190,6 +324,7 @@ pub struct ProposalResponse {
     pub dao: Addr,
     pub title: String,
     pub description: String,
+    pub metadata: Option<ProposalMetadata>,
     pub prop_type: ProposalType,
     pub coins_yes: Uint128,
     pub coins_no: Uint128,
@@ -202,6 +337,15 @@ pub struct ProposalResponse {
     pub voting_end: u64,
     pub concluded: bool,
     pub status: ProposalStatus,
+    pub execution_error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SimulateProposalResponse {
+    pub proposal: ProposalResponse,
+    // Msgs executed when the proposal is concluded successfully
+    pub msgs: Vec<CosmosMsg>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -211,6 +355,62 @@ pub struct ProposalsResponse {
     pub proposals: Vec<ProposalResponse>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VotesByVoterResponse {
+    pub votes: Vec<VoteRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VoterStatsResponse {
+    pub voter: Addr,
+    pub votes: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    // Share of all proposals the voter voted on
+    pub participation_rate: Decimal,
+    pub cycles: Vec<VoterCycleStatsResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VoterCycleStatsResponse {
+    pub cycle: u64,
+    pub votes: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub proposals: u64,
+    pub participation_rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VotingRewardsResponse {
+    pub voter: Addr,
+    // Rewards of ended cycles that have not been claimed yet
+    pub claimable_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VotingRewardPoolResponse {
+    pub cycle: u64,
+    pub amount: Uint128,
+    // Sum of the vote coins of all votes cast in the cycle
+    pub total_vote_coins: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PauseInfoResponse {
+    pub paused: bool,
+    pub paused_until: Option<u64>,
+    pub guardian: Option<Addr>,
+    // Core slot DAOs with an open pause request
+    pub pause_requests: Vec<Addr>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub bjmes_token_addr: Addr,
@@ -224,4 +424,32 @@ pub struct ConfigResponse {
     pub posting_period_length: u64,
     // Length in seconds of the posting period, e.g.  606864 for ~ 1 Week (year/52)
     pub voting_period_length: u64,
+    // Percentage of forfeited proposal deposits paid out to the voters of the cycle, e.g. 10
+    pub voting_reward_percentage: u64,
+    // Address that can pause governance in an emergency
+    pub guardian: Option<Addr>,
+    // Length in seconds after which a pause is lifted automatically
+    pub max_pause_duration: u64,
+    // Max total uluna granted by funding proposals concluded in a cycle, unlimited if not set
+    pub funding_budget_per_cycle: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct OldestOpenProposalResponse {
+    pub id: Option<u64>,
+    pub start_block: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FundingBudgetResponse {
+    pub cycle: u64,
+    // Not set if funding is unlimited
+    pub budget: Option<Uint128>,
+    // Granted in the current cycle
+    pub spent: Uint128,
+    pub remaining: Option<Uint128>,
+    // Granted by passed proposals queued to later cycles
+    pub queued: Uint128,
 }
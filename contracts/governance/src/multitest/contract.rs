@@ -1,14 +1,16 @@
-use cosmwasm_std::{Addr, StdResult, Uint128};
+use cosmwasm_std::{coins, Addr, StdResult, Uint128};
 use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 
 use crate::error::ContractError;
 // use crate::error::ContractError;
 use crate::msg::{
-    CoreSlot, CoreSlotsResponse, ExecuteMsg, InstantiateMsg, PeriodInfoResponse, ProposalResponse,
-    QueryMsg,
+    CoreSlot, CoreSlotsResponse, ExecuteMsg, FundingBudgetResponse, InstantiateMsg,
+    OldestOpenProposalResponse, PauseInfoResponse, PeriodInfoResponse, ProposalMsg,
+    ProposalResponse, QueryMsg, SimulateProposalResponse, VoterStatsResponse, VotesByVoterResponse,
+    VotingRewardPoolResponse, VotingRewardsResponse,
 };
 use crate::state::VoteOption;
-use crate::{execute, instantiate, query};
+use crate::{execute, instantiate, query, reply};
 
 #[derive(Debug, Clone)]
 pub struct GovernanceContract(Addr);
@@ -19,7 +21,7 @@ impl GovernanceContract {
     }
 
     pub fn store_code(app: &mut App) -> u64 {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
         app.store_code(Box::new(contract))
     }
 
@@ -38,6 +40,10 @@ impl GovernanceContract {
         period_start_epoch: u64,
         posting_period_length: u64,
         voting_period_length: u64,
+        voting_reward_percentage: u64,
+        guardian: Option<String>,
+        max_pause_duration: u64,
+        funding_budget_per_cycle: Option<Uint128>,
     ) -> StdResult<Self> {
         app.instantiate_contract(
             code_id,
@@ -51,6 +57,10 @@ impl GovernanceContract {
                 period_start_epoch,
                 posting_period_length,
                 voting_period_length,
+                voting_reward_percentage,
+                guardian,
+                max_pause_duration,
+                funding_budget_per_cycle,
             },
             &[],
             label,
@@ -60,6 +70,24 @@ impl GovernanceContract {
         .map_err(|err| err.downcast().unwrap())
     }
 
+    #[track_caller]
+    pub fn propose(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+
+        proposal_msg: ProposalMsg,
+        deposit: u128,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Propose(proposal_msg),
+            &coins(deposit, "uluna"),
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
     #[track_caller]
     pub fn set_core_slot(
         &self,
@@ -157,6 +185,29 @@ impl GovernanceContract {
         // .map(|_| ())
     }
 
+    #[track_caller]
+    pub fn claim_voting_rewards(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::ClaimVotingRewards { start_after, limit },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn pause(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        app.execute_contract(sender.clone(), self.0.clone(), &ExecuteMsg::Pause {}, &[])
+            .map_err(|err| err.downcast().unwrap())
+    }
+
     #[track_caller]
     pub fn query_period_info(&self, app: &mut App) -> StdResult<PeriodInfoResponse> {
         app.wrap()
@@ -169,11 +220,110 @@ impl GovernanceContract {
             .query_wasm_smart(self.0.clone(), &QueryMsg::Proposal { id })
     }
 
+    #[track_caller]
+    pub fn query_simulate_proposal(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        deposit: u128,
+        proposal: ProposalMsg,
+    ) -> StdResult<SimulateProposalResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::SimulateProposal {
+                sender: sender.to_string(),
+                deposit: Uint128::from(deposit),
+                proposal,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_votes_by_voter(
+        &self,
+        app: &mut App,
+        voter: &Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<VotesByVoterResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::VotesByVoter {
+                voter: voter.to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_voter_stats(
+        &self,
+        app: &mut App,
+        voter: &Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<VoterStatsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::VoterStats {
+                voter: voter.to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_voting_rewards(
+        &self,
+        app: &mut App,
+        voter: &Addr,
+    ) -> StdResult<VotingRewardsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::VotingRewards {
+                voter: voter.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_voting_reward_pool(
+        &self,
+        app: &mut App,
+        cycle: u64,
+    ) -> StdResult<VotingRewardPoolResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::VotingRewardPool { cycle })
+    }
+
+    #[track_caller]
+    pub fn query_pause_info(&self, app: &mut App) -> StdResult<PauseInfoResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::PauseInfo {})
+    }
+
+    #[track_caller]
+    pub fn query_funding_budget(&self, app: &mut App) -> StdResult<FundingBudgetResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::FundingBudget {})
+    }
+
     #[track_caller]
     pub fn query_core_slots(&self, app: &mut App) -> StdResult<CoreSlotsResponse> {
         app.wrap()
             .query_wasm_smart(self.0.clone(), &QueryMsg::CoreSlots {})
     }
+
+    #[track_caller]
+    pub fn query_oldest_open_proposal(
+        &self,
+        app: &mut App,
+    ) -> StdResult<OldestOpenProposalResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::OldestOpenProposal {})
+    }
 }
 
 impl From<GovernanceContract> for Addr {
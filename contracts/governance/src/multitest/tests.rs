@@ -2,24 +2,32 @@
 use cosmwasm_std::{
     coins, from_binary,
     testing::{mock_env, MockApi, MockStorage},
-    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Timestamp, Uint128,
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Timestamp, Uint128, WasmMsg,
 };
+use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg};
 use cw4::Member;
-use cw_multi_test::{next_block, App, AppBuilder, AppResponse, BankKeeper, Executor};
+use cw_multi_test::{
+    next_block, App, AppBuilder, AppResponse, BankKeeper, ContractWrapper, Executor,
+};
 use cw_utils::Duration;
 use dao_members::multitest::contract::DaoMembersContract;
 use dao_multisig::multitest::contract::DaoMultisigContract;
+use distribution::msg::TreasuryAsset;
 use distribution::multitest::contract::DistributionContract;
+use distribution::state::{
+    Approver, Asset, GrantStatus, MilestoneProgress, Recipient, Tranche, VestingSchedule,
+};
 use identityservice::multitest::contract::IdentityserviceContract;
 use jmes::test_utils::get_attribute;
 
 use crate::{
     error::ContractError,
     msg::{
-        CoreSlot, ExecuteMsg, PeriodInfoResponse, ProposalMsg, ProposalPeriod, ProposalResponse,
-        QueryMsg, RevokeCoreSlot,
+        AddGrant, AddGrantMsg, CoreSlot, ExecuteMsg, PeriodInfoResponse, ProposalMetadata,
+        ProposalMsg, ProposalPeriod, ProposalResponse, QueryMsg, RevokeCoreSlot,
+        VoterCycleStatsResponse,
     },
-    state::{ProposalStatus, SlotVoteResult, VoteOption},
+    state::{ProposalStatus, ProposalType, SlotVoteResult, VoteOption, VoteRecord},
 };
 
 use super::contract::GovernanceContract;
@@ -38,6 +46,10 @@ const USER2_VOTING_COINS: u128 = 3000;
 const DISTRIBUTION_INIT_BALANCE: u128 = 10_000_000;
 const GOVERNANCE_INIT_BALANCE: u128 = 100_000; // To test improvement proposal: BankMsg
 
+const MAX_PAUSE_DURATION: u64 = 200;
+const FUNDING_BUDGET_PER_CYCLE: u128 = 1_500_000;
+const MAX_LOCK_DURATION: u64 = 1000;
+
 fn mock_app() -> App {
     let mut env = mock_env();
     env.block.time = Timestamp::from_seconds(EPOCH_START);
@@ -56,18 +68,52 @@ fn mock_app() -> App {
 #[derive(Debug, Clone)]
 struct Contracts {
     governance: GovernanceContract,
-    _bjmes_token: BjmesTokenContract,
+    bjmes_token: BjmesTokenContract,
     distribution: DistributionContract,
     identityservice: IdentityserviceContract,
 }
 
 fn instantiate_contracts(app: &mut App, user1: Addr, user2: Addr, owner: Addr) -> Contracts {
+    instantiate_contracts_with_voting_reward(app, user1, user2, owner, 0)
+}
+
+fn instantiate_contracts_with_voting_reward(
+    app: &mut App,
+    user1: Addr,
+    user2: Addr,
+    owner: Addr,
+    voting_reward_percentage: u64,
+) -> Contracts {
+    instantiate_contracts_with_settings(
+        app,
+        user1,
+        user2,
+        owner,
+        voting_reward_percentage,
+        Some(Uint128::from(FUNDING_BUDGET_PER_CYCLE)),
+    )
+}
+
+fn instantiate_contracts_with_settings(
+    app: &mut App,
+    user1: Addr,
+    user2: Addr,
+    owner: Addr,
+    voting_reward_percentage: u64,
+    funding_budget_per_cycle: Option<Uint128>,
+) -> Contracts {
     // Instantiate needed contracts
 
     let bjmes_code_id = BjmesTokenContract::store_code(app);
-    let bjmes_contract =
-        BjmesTokenContract::instantiate(app, bjmes_code_id, &user1, "bonded JMES Contract")
-            .unwrap();
+    let bjmes_contract = BjmesTokenContract::instantiate(
+        app,
+        bjmes_code_id,
+        &user1,
+        "bonded JMES Contract",
+        None,
+        Some(MAX_LOCK_DURATION),
+    )
+    .unwrap();
 
     let governance_code_id = GovernanceContract::store_code(app);
     let governance_contract = GovernanceContract::instantiate(
@@ -83,9 +129,24 @@ fn instantiate_contracts(app: &mut App, user1: Addr, user2: Addr, owner: Addr) -
         0,
         40,
         40,
+        voting_reward_percentage,
+        Some(owner.to_string()),
+        MAX_PAUSE_DURATION,
+        funding_budget_per_cycle,
     )
     .unwrap();
 
+    // Let user1 mint the voting coins, then hand bJMES over to governance
+    bjmes_contract
+        .add_minter(app, &user1, &user1, None)
+        .unwrap();
+    bjmes_contract
+        .update_governance(app, &user1, Some(governance_contract.addr()))
+        .unwrap();
+    bjmes_contract
+        .update_admin(app, &user1, Some(governance_contract.addr()))
+        .unwrap();
+
     let dao_members_code_id = DaoMembersContract::store_code(app);
     println!("dao_members_code_id: {}", dao_members_code_id);
     let dao_multisig_code_id = DaoMultisigContract::store_code(app);
@@ -175,7 +236,7 @@ fn instantiate_contracts(app: &mut App, user1: Addr, user2: Addr, owner: Addr) -
     let mint3 = bjmes_contract
         .mint(
             app,
-            &user2,
+            &user1,
             user2.clone().into(),
             Uint128::from(USER2_VOTING_COINS),
         )
@@ -191,7 +252,7 @@ fn instantiate_contracts(app: &mut App, user1: Addr, user2: Addr, owner: Addr) -
 
     Contracts {
         governance: governance_contract,
-        _bjmes_token: bjmes_contract,
+        bjmes_token: bjmes_contract,
         distribution: distribution_contract,
         identityservice: identityservice_contract,
     }
@@ -361,6 +422,7 @@ fn set_core_slot_brand_then_revoke_fail_then_revoke() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::CoreSlot {
         title: "Make me CoreTech".into(),
         description: "Serving the chain".into(),
+        metadata: None,
         slot: CoreSlot::Brand {},
     });
 
@@ -398,6 +460,7 @@ fn set_core_slot_brand_then_revoke_fail_then_revoke() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::RevokeCoreSlot {
         title: "Remove Brand Dao".into(),
         description: "Leave it vacant".into(),
+        metadata: None,
         revoke_slot: RevokeCoreSlot {
             slot: CoreSlot::Brand {},
             dao: my_dao_addr.clone().to_string(),
@@ -439,6 +502,7 @@ fn set_core_slot_brand_then_revoke_fail_then_revoke() {
             dao: my_dao_addr.clone(),
             title: "Remove Brand Dao".into(),
             description: "Leave it vacant".into(),
+            metadata: None,
             prop_type: crate::state::ProposalType::RevokeCoreSlot(RevokeCoreSlot {
                 slot: CoreSlot::Brand {},
                 dao: my_dao_addr.clone().into(),
@@ -453,7 +517,8 @@ fn set_core_slot_brand_then_revoke_fail_then_revoke() {
             voting_start: 1660000120,
             voting_end: 1660000160,
             concluded: true,
-            status: ProposalStatus::ExpiredConcluded
+            status: ProposalStatus::ExpiredConcluded,
+            execution_error: None,
         }
     );
 
@@ -513,6 +578,7 @@ fn set_core_slot_brand_then_revoke_fail_then_revoke() {
             dao: my_dao_addr.clone(),
             title: "Remove Brand Dao".into(),
             description: "Leave it vacant".into(),
+            metadata: None,
             prop_type: crate::state::ProposalType::RevokeCoreSlot(RevokeCoreSlot {
                 slot: CoreSlot::Brand {},
                 dao: my_dao_addr.clone().into(),
@@ -527,7 +593,8 @@ fn set_core_slot_brand_then_revoke_fail_then_revoke() {
             voting_start: 1660000200,
             voting_end: 1660000240,
             concluded: true,
-            status: ProposalStatus::SuccessConcluded
+            status: ProposalStatus::SuccessConcluded,
+            execution_error: None,
         }
     );
 
@@ -563,6 +630,7 @@ fn set_core_slot_creative_and_fail_setting_a_second_slot_for_the_same_dao() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::CoreSlot {
         title: "Make me CoreTech".into(),
         description: "Serving the chain".into(),
+        metadata: None,
         slot: CoreSlot::Creative {},
     });
 
@@ -602,6 +670,7 @@ fn set_core_slot_creative_and_fail_setting_a_second_slot_for_the_same_dao() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::CoreSlot {
         title: "Make me CoreTech".into(),
         description: "Serving the chain".into(),
+        metadata: None,
         slot: CoreSlot::Brand {},
     });
 
@@ -662,6 +731,7 @@ fn set_core_slot_tech_and_resign() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::CoreSlot {
         title: "Make me CoreTech".into(),
         description: "Serving the chain".into(),
+        metadata: None,
         slot: CoreSlot::CoreTech {},
     });
 
@@ -777,6 +847,7 @@ fn improvement_bankmsg() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::CoreSlot {
         title: "Make me CoreTech".into(),
         description: "Serving the chain".into(),
+        metadata: None,
         slot: CoreSlot::CoreTech {},
     });
 
@@ -813,6 +884,7 @@ fn improvement_bankmsg() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::Improvement {
         title: "Send funds".into(),
         description: "BankMsg".into(),
+        metadata: None,
         msgs: vec![CosmosMsg::Bank(BankMsg::Send {
             to_address: user1.clone().into(),
             amount: vec![Coin {
@@ -895,6 +967,7 @@ fn improvement_bankmsg_failing() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::CoreSlot {
         title: "Make me CoreTech".into(),
         description: "Serving the chain".into(),
+        metadata: None,
         slot: CoreSlot::CoreTech {},
     });
 
@@ -931,6 +1004,7 @@ fn improvement_bankmsg_failing() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::Improvement {
         title: "Send funds".into(),
         description: "BankMsg".into(),
+        metadata: None,
         msgs: vec![CosmosMsg::Bank(BankMsg::Send {
             to_address: user1.clone().into(),
             amount: vec![Coin {
@@ -1024,8 +1098,11 @@ fn governance_funding_proposal_passing() {
     let proposal_msg = ExecuteMsg::Propose(ProposalMsg::Funding {
         title: "Funding".to_string(),
         description: "Give me money".to_string(),
+        metadata: None,
         duration: FUNDING_DURATION,
         amount: Uint128::from(FUNDING_AMOUNT),
+        vesting: None,
+        asset: None,
     });
 
     // Create, vote on and execute the dao proposal
@@ -1201,7 +1278,7 @@ fn governance_funding_proposal_passing() {
 }
 
 #[test]
-fn governance_funding_proposal_failing() {
+fn revoke_grant_proposal() {
     let mut app = mock_app();
 
     let owner = Addr::unchecked("owner");
@@ -1219,132 +1296,2965 @@ fn governance_funding_proposal_failing() {
     // Create the flex-multisig dao
     let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
 
-    // Governance Proposal Msg
-    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::Funding {
-        title: "Funding".to_string(),
-        description: "Give me money".to_string(),
-        duration: FUNDING_DURATION,
-        amount: Uint128::from(FUNDING_AMOUNT),
-    });
-
-    // Create, vote on and execute the dao proposal
-    DaoMultisigContract::gov_proposal_helper(
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me money".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: None,
+                asset: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
         &mut app,
-        my_dao_addr.clone(),
-        &contracts.governance.addr().clone(),
+        contracts.clone(),
         user1.clone(),
+        VoteOption::Yes,
         user2.clone(),
-        to_binary(&proposal_msg),
-        PROPOSAL_REQUIRED_DEPOSIT,
-    )
-    .unwrap();
+        VoteOption::Yes,
+        1,
+    );
 
-    let period_info_posting = contracts.governance.query_period_info(&mut app).unwrap();
-    println!("\n\n period_info_posting {:?}", period_info_posting);
-    assert_eq!(period_info_posting.current_period, ProposalPeriod::Posting);
+    // Test that only governance can revoke a grant
+    let revoke_err = app
+        .execute_contract(
+            user1.clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::RevokeGrant { grant_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        revoke_err
+            .downcast::<distribution::ContractError>()
+            .unwrap(),
+        distribution::ContractError::Unauthorized {}
+    );
 
-    // Skip period from Posting to VotingBLOKSECNDS
-    app.update_block(|mut block| {
-        block.time = block
-            .time
-            .plus_seconds(period_info_posting.posting_period_length);
-        block.height += period_info_posting.posting_period_length / SECONDS_PER_BLOCK;
+    // Skip a tenth of the grant duration (whole cycles) so part of the grant vests
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 10);
+        block.height += FUNDING_DURATION / 10 / SECONDS_PER_BLOCK;
     });
 
-    let period_info_voting = contracts.governance.query_period_info(&mut app).unwrap();
-    println!("\n\n period_info_voting{:#?}", period_info_voting);
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::RevokeGrant {
+                title: "Revoke".into(),
+                description: "The dao stopped working".into(),
+                metadata: None,
+                grant_id: 1,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    let distribution_balance = app
+        .wrap()
+        .query_balance(contracts.distribution.addr(), "uluna")
+        .unwrap()
+        .amount;
+
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        2,
+    );
 
+    // Test that the vested amount is paid out and the rest stays in the treasury
+    let vested_amount = FUNDING_AMOUNT / 10;
     assert_eq!(
-        period_info_voting,
-        PeriodInfoResponse {
-            current_block: 12355,
-            current_period: ProposalPeriod::Voting,
-            current_time_in_cycle: 60,
-            current_posting_start: 1660000000,
-            current_voting_start: 1660000040,
-            current_voting_end: 1660000080,
-            next_posting_start: 1660000080,
-            next_voting_start: 1660000120,
-            posting_period_length: 40,
-            voting_period_length: 40,
-            cycle_length: 80
-        }
+        app.wrap().query_all_balances(my_dao_addr.clone()).unwrap(),
+        coins(vested_amount + PROPOSAL_REQUIRED_DEPOSIT, "uluna")
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(contracts.distribution.addr(), "uluna")
+            .unwrap()
+            .amount,
+        distribution_balance - Uint128::from(vested_amount)
     );
 
-    // User1 votes no on the governance proposal to fail it
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert!(grant.revoked);
+    assert_eq!(grant.amount_remaining, Uint128::zero());
+    assert_eq!(grant.claimable_amount, Uint128::zero());
+
+    // Test that a revoked grant can't be claimed anymore
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION);
+        block.height += FUNDING_DURATION / SECONDS_PER_BLOCK;
+    });
 
-    let fund_proposal_vote = contracts
+    let claim_err = contracts
+        .distribution
+        .claim(&mut app, &my_dao_addr, 1)
+        .unwrap_err();
+    assert_eq!(claim_err, distribution::ContractError::GrantRevoked {});
+
+    // Test that revoking the grant a second time still concludes and refunds the deposit
+    contracts
         .governance
-        .vote(&mut app, &user1, 1, VoteOption::No)
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::RevokeGrant {
+                title: "Revoke again".into(),
+                description: "The dao stopped working".into(),
+                metadata: None,
+                grant_id: 1,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
         .unwrap();
-    println!("\n\n fund_proposal_vote {:?}", fund_proposal_vote);
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        3,
+    );
 
-    let proposal_result = contracts.governance.query_proposal(&mut app, 1).unwrap();
-    println!("\n\n proposal_result {:?}", proposal_result);
+    let proposal = contracts.governance.query_proposal(&mut app, 3).unwrap();
+    assert!(proposal.concluded);
+    assert_eq!(proposal.status, ProposalStatus::SuccessConcluded);
+    assert!(proposal.execution_error.unwrap().contains("revoke_grant"));
+    assert_eq!(
+        app.wrap().query_all_balances(my_dao_addr).unwrap(),
+        coins(vested_amount + PROPOSAL_REQUIRED_DEPOSIT, "uluna")
+    );
+}
 
-    // Test that you can't conclude a proposal in the voting period
-    let voting_not_ended_err = contracts
-        .governance
-        .conclude(&mut app, &user1, 1)
-        .unwrap_err();
-    assert_eq!(voting_not_ended_err, ContractError::VotingPeriodNotEnded {});
+#[test]
+fn funding_proposal_with_vesting_schedule() {
+    let mut app = mock_app();
 
-    // Skip period from Voting to Posting so we can conclude the prBLOoKSECNDS
-    app.update_block(|mut block| {
-        block.time = block
-            .time
-            .plus_seconds(period_info_posting.voting_period_length);
-        block.height += period_info_posting.voting_period_length / SECONDS_PER_BLOCK;
-    });
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
 
-    let period_info_posting2 = contracts.governance.query_period_info(&mut app).unwrap();
-    println!("\n\n period_info_posting2 {:?}", period_info_posting2);
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
 
-    let conclude_proposal_result = contracts.governance.conclude(&mut app, &user1, 1).unwrap();
-    println!(
-        "\n\n conclude_proposal_result {:?}",
-        conclude_proposal_result
-    );
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
 
-    // Test that you can't conclude a proposal (and execute its msgs) a second time
-    let conclude2_proposal_result = contracts
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Test that tranches have to add up to the grant amount
+    let tranches_err = contracts
         .governance
-        .conclude(&mut app, &user1, 1)
+        .query_simulate_proposal(
+            &mut app,
+            &my_dao_addr,
+            PROPOSAL_REQUIRED_DEPOSIT,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me money in tranches".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: Some(VestingSchedule::Tranches {
+                    tranches: vec![Tranche {
+                        release_after: FUNDING_DURATION / 2,
+                        amount: Uint128::from(FUNDING_AMOUNT / 2),
+                    }],
+                }),
+                asset: None,
+            },
+        )
         .unwrap_err();
-    assert_eq!(
-        conclude2_proposal_result,
-        ContractError::ProposalAlreadyConcluded {}
-    );
-    println!(
-        "\n\n conclude2_proposal_result {:?}",
-        conclude2_proposal_result
+    assert!(tranches_err
+        .to_string()
+        .contains("Tranches must add up to the grant amount"));
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me money after a cliff".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: Some(VestingSchedule::Cliff {
+                    cliff: FUNDING_DURATION / 2,
+                }),
+                asset: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
     );
 
-    // Skip half the grant duration time to allow us to test if the failing proposal lets us claim funds
-    app.update_block(|mut block| {
-        block.time = block.time.plus_seconds(FUNDING_DURATION / 2);
-        block.height += FUNDING_DURATION / 2 / SECONDS_PER_BLOCK;
+    // Test that nothing vests before the cliff
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 4);
+        block.height += FUNDING_DURATION / 4 / SECONDS_PER_BLOCK;
     });
 
-    let claim_funds_err = contracts
+    let grant = contracts
         .distribution
-        .claim(&mut app, &user1, 1)
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.claimable_amount, Uint128::zero());
+
+    let claim_err = contracts
+        .distribution
+        .claim(&mut app, &my_dao_addr, 1)
         .unwrap_err();
+    assert_eq!(claim_err, distribution::ContractError::AmountTooSmall {});
+
+    // Test that the linearly vested amount is released at the cliff
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 4);
+        block.height += FUNDING_DURATION / 4 / SECONDS_PER_BLOCK;
+    });
+
+    contracts
+        .distribution
+        .claim(&mut app, &my_dao_addr, 1)
+        .unwrap();
     assert_eq!(
-        claim_funds_err,
-        distribution::ContractError::GrantNotFound {}
+        app.wrap().query_all_balances(my_dao_addr.clone()).unwrap(),
+        coins(FUNDING_AMOUNT / 2 + PROPOSAL_REQUIRED_DEPOSIT, "uluna")
     );
+}
 
-    assert_eq!(
-        app.wrap()
-            .query_all_balances(Addr::unchecked(my_dao_addr.clone()))
-            .unwrap(),
-        vec![]
+#[test]
+fn funding_proposal_in_cw20_tokens() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    // The funding budget only allows uluna grants
+    let contracts = instantiate_contracts_with_settings(
+        &mut app,
+        user1.clone(),
+        user2.clone(),
+        owner.clone(),
+        0,
+        None,
     );
-    assert_eq!(
-        app.wrap()
-            .query_all_balances(contracts.distribution.addr().clone())
-            .unwrap(),
-        coins(DISTRIBUTION_INIT_BALANCE, "uluna")
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Fund the distribution contract with cw20 tokens
+    let cw20_code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    )));
+    let token_addr = app
+        .instantiate_contract(
+            cw20_code_id,
+            owner.clone(),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Grant Token".to_string(),
+                symbol: "GRANT".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: contracts.distribution.addr().to_string(),
+                    amount: Uint128::from(FUNDING_AMOUNT),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "Grant Token",
+            None,
+        )
+        .unwrap();
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me tokens".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: None,
+                asset: Some(Asset::Cw20 {
+                    contract_addr: token_addr.clone(),
+                }),
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
     );
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        grant.asset,
+        Asset::Cw20 {
+            contract_addr: token_addr.clone()
+        }
+    );
+
+    // Skip the grant duration to claim all tokens
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION);
+        block.height += FUNDING_DURATION / SECONDS_PER_BLOCK;
+    });
+
+    contracts
+        .distribution
+        .claim(&mut app, &my_dao_addr, 1)
+        .unwrap();
+
+    let balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token_addr,
+            &Cw20QueryMsg::Balance {
+                address: my_dao_addr.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::from(FUNDING_AMOUNT));
+
+    // Test that only the deposit refund was paid in uluna
+    assert_eq!(
+        app.wrap().query_all_balances(my_dao_addr.clone()).unwrap(),
+        coins(PROPOSAL_REQUIRED_DEPOSIT, "uluna")
+    );
+}
+
+#[test]
+fn treasury_solvency() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let balance = Uint128::from(DISTRIBUTION_INIT_BALANCE - PROPOSAL_REQUIRED_DEPOSIT);
+    let treasury = contracts.distribution.query_treasury(&mut app).unwrap();
+    assert_eq!(
+        treasury.assets,
+        vec![TreasuryAsset {
+            asset: Asset::default(),
+            balance,
+            committed: Uint128::zero(),
+            free: balance,
+        }]
+    );
+
+    // Test that a funding proposal can't ask for more than the free balance
+    let funding_err = contracts
+        .governance
+        .query_simulate_proposal(
+            &mut app,
+            &my_dao_addr,
+            PROPOSAL_REQUIRED_DEPOSIT,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me everything".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: balance + Uint128::one(),
+                vesting: None,
+                asset: None,
+            },
+        )
+        .unwrap_err();
+    assert!(funding_err.to_string().contains("is free in the treasury"));
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me money".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: None,
+                asset: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
+    );
+
+    let treasury = contracts.distribution.query_treasury(&mut app).unwrap();
+    assert_eq!(treasury.assets[0].balance, balance);
+    assert_eq!(treasury.assets[0].committed, Uint128::from(FUNDING_AMOUNT));
+    assert_eq!(
+        treasury.assets[0].free,
+        balance - Uint128::from(FUNDING_AMOUNT)
+    );
+
+    // Test that the distribution contract rejects grants over-committing the treasury
+    let add_grant_err = app
+        .execute_contract(
+            contracts.governance.addr().clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::AddGrant {
+                dao: my_dao_addr.clone(),
+                duration: FUNDING_DURATION,
+                amount: balance,
+                vesting: None,
+                asset: None,
+                start: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        add_grant_err
+            .downcast::<distribution::ContractError>()
+            .unwrap(),
+        distribution::ContractError::InsufficientTreasury {
+            asset: "uluna".to_string(),
+            free: balance - Uint128::from(FUNDING_AMOUNT),
+        }
+    );
+
+    // Test that claims release the commitment
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 2);
+        block.height += FUNDING_DURATION / 2 / SECONDS_PER_BLOCK;
+    });
+
+    contracts
+        .distribution
+        .claim(&mut app, &my_dao_addr, 1)
+        .unwrap();
+
+    let treasury = contracts.distribution.query_treasury(&mut app).unwrap();
+    assert_eq!(
+        treasury.assets[0].balance,
+        balance - Uint128::from(FUNDING_AMOUNT / 2)
+    );
+    assert_eq!(
+        treasury.assets[0].committed,
+        Uint128::from(FUNDING_AMOUNT / 2)
+    );
+    assert_eq!(
+        treasury.assets[0].free,
+        balance - Uint128::from(FUNDING_AMOUNT)
+    );
+}
+
+#[test]
+fn funding_proposal_exceeding_treasury_when_concluded() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let balance = Uint128::from(DISTRIBUTION_INIT_BALANCE - PROPOSAL_REQUIRED_DEPOSIT);
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me money".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: None,
+                asset: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(40);
+        block.height += 40 / SECONDS_PER_BLOCK;
+    });
+
+    contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::Yes)
+        .unwrap();
+
+    // Another grant takes most of the treasury while the proposal is voted on
+    app.execute_contract(
+        contracts.governance.addr().clone(),
+        contracts.distribution.addr().clone(),
+        &distribution::msg::ExecuteMsg::AddGrant {
+            dao: my_dao_addr.clone(),
+            duration: FUNDING_DURATION,
+            amount: balance - Uint128::from(FUNDING_AMOUNT / 2),
+            vesting: None,
+            asset: None,
+            start: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(40);
+        block.height += 40 / SECONDS_PER_BLOCK;
+    });
+
+    // Test that the proposal still concludes and refunds the deposit, but is marked unfunded
+    contracts.governance.conclude(&mut app, &user1, 1).unwrap();
+
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert!(proposal.concluded);
+    assert_eq!(proposal.status, ProposalStatus::SuccessConcluded);
+    assert!(proposal.execution_error.unwrap().contains("add_grant"));
+
+    assert_eq!(
+        app.wrap()
+            .query_balance(&my_dao_addr, "uluna")
+            .unwrap()
+            .amount,
+        Uint128::from(PROPOSAL_REQUIRED_DEPOSIT)
+    );
+
+    // Test that no grant was added and the booked budget was released
+    assert!(contracts
+        .distribution
+        .query_grant(&mut app, 2)
+        .unwrap()
+        .is_none());
+
+    let budget = contracts.governance.query_funding_budget(&mut app).unwrap();
+    assert_eq!(budget.spent, Uint128::zero());
+    assert_eq!(budget.queued, Uint128::zero());
+}
+
+#[test]
+fn grants_query_status_filters() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Add 11 grants directly as governance, grant 2 expires quickly
+    for grant_id in 1..=11u64 {
+        let (duration, amount) = match grant_id {
+            2 => (100, FUNDING_AMOUNT / 10),
+            _ => (FUNDING_DURATION, FUNDING_AMOUNT / 100),
+        };
+        app.execute_contract(
+            contracts.governance.addr().clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::AddGrant {
+                dao: my_dao_addr.clone(),
+                duration,
+                amount: Uint128::from(amount),
+                vesting: None,
+                asset: None,
+                start: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        contracts.governance.addr().clone(),
+        contracts.distribution.addr().clone(),
+        &distribution::msg::ExecuteMsg::RevokeGrant { grant_id: 3 },
+        &[],
+    )
+    .unwrap();
+
+    // Test that grants are ordered by numeric id
+    let grants = contracts
+        .distribution
+        .query_grants(&mut app, None, None, None, Some(30))
+        .unwrap()
+        .grants;
+    assert_eq!(
+        grants.iter().map(|g| g.grant_id).collect::<Vec<_>>(),
+        (1..=11).collect::<Vec<_>>()
+    );
+
+    let grants = contracts
+        .distribution
+        .query_grants(&mut app, Some(my_dao_addr.clone()), None, Some(9), None)
+        .unwrap()
+        .grants;
+    assert_eq!(
+        grants.iter().map(|g| g.grant_id).collect::<Vec<_>>(),
+        vec![10, 11]
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(100);
+        block.height += 100 / SECONDS_PER_BLOCK;
+    });
+
+    let expired = contracts
+        .distribution
+        .query_grants(&mut app, None, Some(GrantStatus::Expired), None, None)
+        .unwrap()
+        .grants;
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].grant_id, 2);
+    assert_eq!(expired[0].status, GrantStatus::Expired);
+    assert_eq!(
+        expired[0].claimable_amount,
+        Uint128::from(FUNDING_AMOUNT / 10)
+    );
+
+    contracts
+        .distribution
+        .claim(&mut app, &my_dao_addr, 2)
+        .unwrap();
+
+    let fully_claimed = contracts
+        .distribution
+        .query_grants(&mut app, None, Some(GrantStatus::FullyClaimed), None, None)
+        .unwrap()
+        .grants;
+    assert_eq!(
+        fully_claimed.iter().map(|g| g.grant_id).collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    let revoked = contracts
+        .distribution
+        .query_grants(&mut app, None, Some(GrantStatus::Revoked), None, None)
+        .unwrap()
+        .grants;
+    assert_eq!(
+        revoked.iter().map(|g| g.grant_id).collect::<Vec<_>>(),
+        vec![3]
+    );
+
+    let active = contracts
+        .distribution
+        .query_grants(
+            &mut app,
+            Some(my_dao_addr.clone()),
+            Some(GrantStatus::Active),
+            None,
+            Some(30),
+        )
+        .unwrap()
+        .grants;
+    assert_eq!(
+        active.iter().map(|g| g.grant_id).collect::<Vec<_>>(),
+        vec![1, 4, 5, 6, 7, 8, 9, 10, 11]
+    );
+}
+
+#[test]
+fn claim_all_grants_of_a_dao() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Add three grants directly as governance, the last one is too small to claim
+    for amount in [FUNDING_AMOUNT, FUNDING_AMOUNT / 2, FUNDING_AMOUNT / 100] {
+        app.execute_contract(
+            contracts.governance.addr().clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::AddGrant {
+                dao: my_dao_addr.clone(),
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(amount),
+                vesting: None,
+                asset: None,
+                start: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Test that nothing can be claimed before enough has vested
+    let claim_err = contracts
+        .distribution
+        .claim_all(&mut app, &user1, &my_dao_addr)
+        .unwrap_err();
+    assert_eq!(claim_err, distribution::ContractError::AmountTooSmall {});
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 2);
+        block.height += FUNDING_DURATION / 2 / SECONDS_PER_BLOCK;
+    });
+
+    let claim_all_result = contracts
+        .distribution
+        .claim_all(&mut app, &user1, &my_dao_addr)
+        .unwrap();
+
+    // Test that both claimable grants are paid out with a single transfer
+    assert_eq!(
+        claim_all_result
+            .events
+            .iter()
+            .filter(|event| event.ty == "transfer")
+            .count(),
+        1
+    );
+    assert_eq!(
+        get_attribute(&claim_all_result, "wasm", "grant_1_claimed_amount"),
+        (FUNDING_AMOUNT / 2).to_string()
+    );
+    assert_eq!(
+        get_attribute(&claim_all_result, "wasm", "grant_2_claimed_amount"),
+        (FUNDING_AMOUNT / 4).to_string()
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(my_dao_addr.clone()).unwrap(),
+        coins(
+            FUNDING_AMOUNT / 2 + FUNDING_AMOUNT / 4 + PROPOSAL_REQUIRED_DEPOSIT,
+            "uluna"
+        )
+    );
+
+    // The small grant keeps vesting until it can be claimed
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 3)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.amount_remaining, Uint128::from(FUNDING_AMOUNT / 100));
+}
+
+#[test]
+fn grant_recipient_split() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    app.execute_contract(
+        contracts.governance.addr().clone(),
+        contracts.distribution.addr().clone(),
+        &distribution::msg::ExecuteMsg::AddGrant {
+            dao: my_dao_addr.clone(),
+            duration: FUNDING_DURATION,
+            amount: Uint128::from(FUNDING_AMOUNT),
+            vesting: None,
+            asset: None,
+            start: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let split = vec![
+        Recipient {
+            address: user1.clone(),
+            weight: 1,
+        },
+        Recipient {
+            address: user2.clone(),
+            weight: 3,
+        },
+    ];
+
+    // Test that only the grantee dao can split the grant
+    let split_err = contracts
+        .distribution
+        .set_grant_split(&mut app, &user1, 1, split.clone())
+        .unwrap_err();
+    assert_eq!(split_err, distribution::ContractError::Unauthorized {});
+
+    let split_err = contracts
+        .distribution
+        .set_grant_split(
+            &mut app,
+            &my_dao_addr,
+            1,
+            vec![split[0].clone(), split[0].clone()],
+        )
+        .unwrap_err();
+    assert_eq!(
+        split_err,
+        distribution::ContractError::InvalidSplit {
+            error: "Duplicate recipient user1".to_string()
+        }
+    );
+
+    let split_err = contracts
+        .distribution
+        .set_grant_split(
+            &mut app,
+            &my_dao_addr,
+            1,
+            vec![Recipient {
+                address: user1.clone(),
+                weight: u64::MAX,
+            }],
+        )
+        .unwrap_err();
+    assert_eq!(
+        split_err,
+        distribution::ContractError::InvalidSplit {
+            error: "Recipient weights can be at most 1000000".to_string()
+        }
+    );
+
+    contracts
+        .distribution
+        .set_grant_split(&mut app, &my_dao_addr, 1, split.clone())
+        .unwrap();
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.recipients, split);
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 2);
+        block.height += FUNDING_DURATION / 2 / SECONDS_PER_BLOCK;
+    });
+
+    contracts.distribution.claim(&mut app, &user1, 1).unwrap();
+
+    // Test that the claimed half is split 1:3 and nothing goes to the dao
+    assert_eq!(
+        app.wrap().query_all_balances(user1.clone()).unwrap(),
+        coins(FUNDING_AMOUNT / 8, "uluna")
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(user2.clone()).unwrap(),
+        coins(FUNDING_AMOUNT * 3 / 8, "uluna")
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(my_dao_addr.clone()).unwrap(),
+        coins(PROPOSAL_REQUIRED_DEPOSIT, "uluna")
+    );
+}
+
+#[test]
+fn milestone_grant_sign_off() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let reviewer = Addr::unchecked("reviewer");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let add_milestone_grant = |app: &mut App, approver: Approver| {
+        app.execute_contract(
+            contracts.governance.addr().clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::AddGrant {
+                dao: my_dao_addr.clone(),
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: Some(VestingSchedule::Milestones {
+                    approver,
+                    amounts: vec![Uint128::from(600_000u128), Uint128::from(400_000u128)],
+                }),
+                asset: None,
+                start: None,
+            },
+            &[],
+        )
+    };
+
+    // Test that milestones must add up to the grant amount
+    let add_err = app
+        .execute_contract(
+            contracts.governance.addr().clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::AddGrant {
+                dao: my_dao_addr.clone(),
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: Some(VestingSchedule::Milestones {
+                    approver: Approver::Governance {},
+                    amounts: vec![Uint128::from(600_000u128)],
+                }),
+                asset: None,
+                start: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        add_err.downcast::<distribution::ContractError>().unwrap(),
+        distribution::ContractError::InvalidVestingSchedule {
+            error: "Generic error: Milestones must add up to the grant amount".to_string()
+        }
+    );
+
+    add_milestone_grant(
+        &mut app,
+        Approver::Reviewer {
+            address: reviewer.clone(),
+        },
+    )
+    .unwrap();
+
+    let report_hash = "a".repeat(64);
+
+    // Test that only the grantee dao can report on a milestone
+    let report_err = contracts
+        .distribution
+        .submit_milestone_report(&mut app, &user1, 1, 0, &report_hash)
+        .unwrap_err();
+    assert_eq!(report_err, distribution::ContractError::Unauthorized {});
+
+    let report_err = contracts
+        .distribution
+        .submit_milestone_report(&mut app, &my_dao_addr, 1, 0, "not a hash")
+        .unwrap_err();
+    assert_eq!(
+        report_err,
+        distribution::ContractError::InvalidReportHash {}
+    );
+
+    let report_err = contracts
+        .distribution
+        .submit_milestone_report(&mut app, &my_dao_addr, 1, 2, &report_hash)
+        .unwrap_err();
+    assert_eq!(
+        report_err,
+        distribution::ContractError::MilestoneNotFound {}
+    );
+
+    // Test that a milestone can't be approved before it's reported
+    let approve_err = contracts
+        .distribution
+        .approve_milestone(&mut app, &reviewer, 1, 0)
+        .unwrap_err();
+    assert_eq!(
+        approve_err,
+        distribution::ContractError::MilestoneReportMissing {}
+    );
+
+    contracts
+        .distribution
+        .submit_milestone_report(&mut app, &my_dao_addr, 1, 0, &report_hash)
+        .unwrap();
+
+    // Test that only the designated reviewer can approve
+    let approve_err = contracts
+        .distribution
+        .approve_milestone(&mut app, &owner, 1, 0)
+        .unwrap_err();
+    assert_eq!(approve_err, distribution::ContractError::Unauthorized {});
+
+    // Nothing unlocks with time alone, even after the grant expired
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION);
+        block.height += FUNDING_DURATION / SECONDS_PER_BLOCK;
+    });
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.claimable_amount, Uint128::zero());
+
+    contracts
+        .distribution
+        .approve_milestone(&mut app, &reviewer, 1, 0)
+        .unwrap();
+
+    let approve_err = contracts
+        .distribution
+        .approve_milestone(&mut app, &reviewer, 1, 0)
+        .unwrap_err();
+    assert_eq!(
+        approve_err,
+        distribution::ContractError::MilestoneAlreadyApproved {}
+    );
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.claimable_amount, Uint128::from(600_000u128));
+    assert_eq!(
+        grant.milestones,
+        vec![
+            MilestoneProgress {
+                report_hash: Some(report_hash.clone()),
+                approved: true
+            },
+            MilestoneProgress::default()
+        ]
+    );
+
+    contracts.distribution.claim(&mut app, &user1, 1).unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(my_dao_addr.clone()).unwrap(),
+        coins(1000 + 600_000, "uluna")
+    );
+
+    // Test that milestones signed off by governance are approved by the governance contract
+    add_milestone_grant(&mut app, Approver::Governance {}).unwrap();
+
+    contracts
+        .distribution
+        .submit_milestone_report(&mut app, &my_dao_addr, 2, 1, &report_hash)
+        .unwrap();
+
+    let approve_err = contracts
+        .distribution
+        .approve_milestone(&mut app, &reviewer, 2, 1)
+        .unwrap_err();
+    assert_eq!(approve_err, distribution::ContractError::Unauthorized {});
+
+    contracts
+        .distribution
+        .approve_milestone(&mut app, contracts.governance.addr(), 2, 1)
+        .unwrap();
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 2)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.claimable_amount, Uint128::from(400_000u128));
+
+    // Test that approving an unreported milestone by proposal still concludes and refunds the
+    // deposit
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::ApproveMilestone {
+                title: "Approve".into(),
+                description: "The first milestone is done".into(),
+                metadata: None,
+                grant_id: 2,
+                milestone: 0,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
+    );
+
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert!(proposal.concluded);
+    assert_eq!(proposal.status, ProposalStatus::SuccessConcluded);
+    assert!(proposal
+        .execution_error
+        .unwrap()
+        .contains("approve_milestone"));
+    assert_eq!(
+        app.wrap().query_all_balances(my_dao_addr.clone()).unwrap(),
+        coins(1000 + 600_000, "uluna")
+    );
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 2)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.milestones[0], MilestoneProgress::default());
+
+    // Test that regular grants have no milestones to report on
+    app.execute_contract(
+        contracts.governance.addr().clone(),
+        contracts.distribution.addr().clone(),
+        &distribution::msg::ExecuteMsg::AddGrant {
+            dao: my_dao_addr.clone(),
+            duration: FUNDING_DURATION,
+            amount: Uint128::from(FUNDING_AMOUNT),
+            vesting: None,
+            asset: None,
+            start: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let report_err = contracts
+        .distribution
+        .submit_milestone_report(&mut app, &my_dao_addr, 3, 0, &report_hash)
+        .unwrap_err();
+    assert_eq!(
+        report_err,
+        distribution::ContractError::NotMilestoneGrant {}
+    );
+}
+
+#[test]
+fn pause_and_resume_grant_proposals() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me money".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: None,
+                asset: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
+    );
+
+    // Test that only governance can pause a grant
+    let pause_err = app
+        .execute_contract(
+            user1.clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::PauseGrant { grant_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        pause_err.downcast::<distribution::ContractError>().unwrap(),
+        distribution::ContractError::Unauthorized {}
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 10);
+        block.height += FUNDING_DURATION / 10 / SECONDS_PER_BLOCK;
+    });
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::PauseGrant {
+                title: "Pause".into(),
+                description: "The dao is under investigation".into(),
+                metadata: None,
+                grant_id: 1,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        2,
+    );
+
+    let paused_grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(paused_grant.status, GrantStatus::Paused);
+    assert!(paused_grant.paused_at.is_some());
+    assert!(!paused_grant.claimable_amount.is_zero());
+
+    // Test that nothing can be claimed and vesting stands still while paused
+    let claim_err = contracts
+        .distribution
+        .claim(&mut app, &user1, 1)
+        .unwrap_err();
+    assert_eq!(claim_err, distribution::ContractError::GrantPaused {});
+
+    let claim_all_err = contracts
+        .distribution
+        .claim_all(&mut app, &user1, &my_dao_addr)
+        .unwrap_err();
+    assert_eq!(
+        claim_all_err,
+        distribution::ContractError::AmountTooSmall {}
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION);
+        block.height += FUNDING_DURATION / SECONDS_PER_BLOCK;
+    });
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.status, GrantStatus::Paused);
+    assert_eq!(grant.claimable_amount, paused_grant.claimable_amount);
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::ResumeGrant {
+                title: "Resume".into(),
+                description: "The investigation found nothing".into(),
+                metadata: None,
+                grant_id: 1,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        3,
+    );
+
+    // Test that the expiry is extended by the paused duration and vesting resumes
+    let resumed_at = app.block_info().time;
+    let paused_seconds = resumed_at.seconds() - paused_grant.paused_at.unwrap().seconds();
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.status, GrantStatus::Active);
+    assert_eq!(grant.paused_at, None);
+    assert_eq!(grant.paused_duration, paused_seconds);
+    assert_eq!(
+        grant.expires,
+        paused_grant.expires.plus_seconds(paused_seconds)
+    );
+    assert_eq!(grant.claimable_amount, paused_grant.claimable_amount);
+
+    let resume_err = app
+        .execute_contract(
+            contracts.governance.addr().clone(),
+            contracts.distribution.addr().clone(),
+            &distribution::msg::ExecuteMsg::ResumeGrant { grant_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        resume_err
+            .downcast::<distribution::ContractError>()
+            .unwrap(),
+        distribution::ContractError::GrantNotPaused {}
+    );
+
+    // Test that resuming the grant a second time still concludes and refunds the deposit
+    let dao_balance = app
+        .wrap()
+        .query_balance(&my_dao_addr, "uluna")
+        .unwrap()
+        .amount;
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::ResumeGrant {
+                title: "Resume again".into(),
+                description: "The investigation found nothing".into(),
+                metadata: None,
+                grant_id: 1,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        4,
+    );
+
+    let proposal = contracts.governance.query_proposal(&mut app, 4).unwrap();
+    assert!(proposal.concluded);
+    assert_eq!(proposal.status, ProposalStatus::SuccessConcluded);
+    assert!(proposal.execution_error.unwrap().contains("resume_grant"));
+    assert_eq!(
+        app.wrap()
+            .query_balance(&my_dao_addr, "uluna")
+            .unwrap()
+            .amount,
+        dao_balance
+    );
+
+    // Test that the full grant vests by the extended expiry
+    app.update_block(|block| {
+        block.time = grant.expires;
+        block.height += FUNDING_DURATION / SECONDS_PER_BLOCK;
+    });
+
+    contracts.distribution.claim(&mut app, &user1, 1).unwrap();
+
+    let grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(grant.status, GrantStatus::FullyClaimed);
+}
+
+#[test]
+fn funding_budget_per_cycle() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let funding = |amount: u128| ProposalMsg::Funding {
+        title: "Funding".into(),
+        description: "Give me money".into(),
+        metadata: None,
+        duration: FUNDING_DURATION,
+        amount: Uint128::from(amount),
+        vesting: None,
+        asset: None,
+    };
+
+    // Test that a single grant can't exceed the budget of a cycle
+    let funding_err = contracts
+        .governance
+        .query_simulate_proposal(
+            &mut app,
+            &my_dao_addr,
+            PROPOSAL_REQUIRED_DEPOSIT,
+            funding(FUNDING_BUDGET_PER_CYCLE + 1),
+        )
+        .unwrap_err();
+    assert!(funding_err
+        .to_string()
+        .contains("Funding is limited to 1500000 uluna per cycle"));
+
+    // Test that grants in other assets can't bypass the budget
+    let funding_err = contracts
+        .governance
+        .query_simulate_proposal(
+            &mut app,
+            &my_dao_addr,
+            PROPOSAL_REQUIRED_DEPOSIT,
+            ProposalMsg::Funding {
+                title: "Funding".into(),
+                description: "Give me tokens".into(),
+                metadata: None,
+                duration: FUNDING_DURATION,
+                amount: Uint128::from(FUNDING_AMOUNT),
+                vesting: None,
+                asset: Some(Asset::Cw20 {
+                    contract_addr: Addr::unchecked("token"),
+                }),
+            },
+        )
+        .unwrap_err();
+    assert!(funding_err
+        .to_string()
+        .contains("Only uluna can be granted while funding is limited per cycle"));
+
+    // Fund the dao with a second deposit
+    app.send_tokens(
+        contracts.distribution.addr().clone(),
+        my_dao_addr.clone(),
+        &coins(PROPOSAL_REQUIRED_DEPOSIT, "uluna"),
+    )
+    .unwrap();
+
+    // Two grants together exceeding the budget pass in the same cycle
+    for _ in 1..=2 {
+        contracts
+            .governance
+            .propose(
+                &mut app,
+                &my_dao_addr,
+                funding(FUNDING_AMOUNT),
+                PROPOSAL_REQUIRED_DEPOSIT,
+            )
+            .unwrap();
+    }
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(40);
+        block.height += 40 / SECONDS_PER_BLOCK;
+    });
+
+    for id in 1..=2 {
+        contracts
+            .governance
+            .vote(&mut app, &user1, id, VoteOption::Yes)
+            .unwrap();
+    }
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(40);
+        block.height += 40 / SECONDS_PER_BLOCK;
+    });
+
+    let period_info = contracts.governance.query_period_info(&mut app).unwrap();
+
+    let res = contracts.governance.conclude(&mut app, &user1, 1).unwrap();
+    let funding_cycle: u64 = get_attribute(&res, "wasm", "funding_cycle")
+        .parse()
+        .unwrap();
+
+    // Test that the second grant is queued to the next cycle's budget
+    let res = contracts.governance.conclude(&mut app, &user1, 2).unwrap();
+    assert_eq!(
+        get_attribute(&res, "wasm", "funding_cycle"),
+        (funding_cycle + 1).to_string()
+    );
+
+    let first_grant = contracts
+        .distribution
+        .query_grant(&mut app, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(first_grant.started, app.block_info().time);
+
+    let queued_grant = contracts
+        .distribution
+        .query_grant(&mut app, 2)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        queued_grant.started,
+        Timestamp::from_seconds(period_info.next_posting_start)
+    );
+    assert_eq!(
+        queued_grant.expires,
+        queued_grant.started.plus_seconds(FUNDING_DURATION)
+    );
+
+    let budget = contracts.governance.query_funding_budget(&mut app).unwrap();
+    assert_eq!(budget.cycle, funding_cycle);
+    assert_eq!(budget.budget, Some(Uint128::from(FUNDING_BUDGET_PER_CYCLE)));
+    assert_eq!(budget.spent, Uint128::from(FUNDING_AMOUNT));
+    assert_eq!(
+        budget.remaining,
+        Some(Uint128::from(FUNDING_BUDGET_PER_CYCLE - FUNDING_AMOUNT))
+    );
+    assert_eq!(budget.queued, Uint128::from(FUNDING_AMOUNT));
+
+    // Test that the queued grant counts towards the budget once its cycle starts
+    app.update_block(|block| {
+        block.time = Timestamp::from_seconds(period_info.next_posting_start);
+        block.height += 80 / SECONDS_PER_BLOCK;
+    });
+
+    let budget = contracts.governance.query_funding_budget(&mut app).unwrap();
+    assert_eq!(budget.cycle, funding_cycle + 1);
+    assert_eq!(budget.spent, Uint128::from(FUNDING_AMOUNT));
+    assert_eq!(budget.queued, Uint128::zero());
+}
+
+#[test]
+fn locked_bjmes_voting_power() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Test that locks can't exceed the max duration
+    let lock_err = contracts
+        .bjmes_token
+        .lock(
+            &mut app,
+            &user1,
+            Uint128::from(USER1_VOTING_COINS / 2),
+            MAX_LOCK_DURATION + 1,
+        )
+        .unwrap_err();
+    assert_eq!(
+        lock_err,
+        bjmes_token::ContractError::LockTooLong {
+            max: MAX_LOCK_DURATION
+        }
+    );
+
+    // Lock half of user1's bJMES for the max duration
+    let lock_height = app.block_info().height;
+    contracts
+        .bjmes_token
+        .lock(
+            &mut app,
+            &user1,
+            Uint128::from(USER1_VOTING_COINS / 2),
+            MAX_LOCK_DURATION,
+        )
+        .unwrap();
+
+    // Test that a lock can't be shortened
+    let lock_err = contracts
+        .bjmes_token
+        .extend_lock(&mut app, &user1, MAX_LOCK_DURATION / 2)
+        .unwrap_err();
+    assert_eq!(
+        lock_err,
+        bjmes_token::ContractError::LockCannotBeShortened {}
+    );
+
+    // Skip a tenth of the lock (and whole cycles) so part of the bonus has decayed
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(6 * 80);
+        block.height += MAX_LOCK_DURATION / 10;
+    });
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Text".into(),
+                description: "Locked voting power".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    let remaining = lock_height + MAX_LOCK_DURATION - proposal.start_block;
+    let voting_power = USER1_VOTING_COINS
+        + Uint128::from(USER1_VOTING_COINS / 2)
+            .multiply_ratio(remaining, MAX_LOCK_DURATION)
+            .u128();
+    assert!(voting_power > USER1_VOTING_COINS);
+    assert_eq!(
+        contracts
+            .bjmes_token
+            .query_voting_power_at(&mut app, &user1, proposal.start_block)
+            .unwrap(),
+        Uint128::from(voting_power)
+    );
+
+    // Test that the vote is weighted by the voting power at the start of the proposal
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
+    );
+
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert_eq!(proposal.coins_yes, Uint128::from(voting_power));
+}
+
+#[test]
+fn transfer_whitelist_proposal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let pool = contracts.distribution.addr().clone();
+
+    // Test that bJMES can't be moved before the pool is whitelisted
+    let transfer_err = contracts
+        .bjmes_token
+        .transfer(&mut app, &user1, &pool, Uint128::from(500u128))
+        .unwrap_err();
+    assert_eq!(transfer_err, bjmes_token::ContractError::NotWhitelisted {});
+
+    // Test that only governance can update the whitelist
+    let update_err = contracts
+        .bjmes_token
+        .update_admin(&mut app, &user1, Some(&user1))
+        .unwrap_err();
+    assert_eq!(update_err.to_string(), "Caller is not admin");
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TransferWhitelist {
+                title: "Whitelist".into(),
+                description: "Let the distribution contract hold bJMES".into(),
+                metadata: None,
+                add: vec![pool.to_string()],
+                remove: vec![],
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
+    );
+
+    assert_eq!(
+        contracts
+            .bjmes_token
+            .query_transfer_whitelist(&mut app)
+            .unwrap(),
+        vec![pool.to_string()]
+    );
+
+    // Test that holders can send to the whitelisted pool but not to each other
+    contracts
+        .bjmes_token
+        .transfer(&mut app, &user1, &pool, Uint128::from(500u128))
+        .unwrap();
+
+    let balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contracts.bjmes_token.addr(),
+            &Cw20QueryMsg::Balance {
+                address: pool.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::from(500u128));
+
+    let transfer_err = contracts
+        .bjmes_token
+        .transfer(&mut app, &user1, &user2, Uint128::from(500u128))
+        .unwrap_err();
+    assert_eq!(transfer_err, bjmes_token::ContractError::NotWhitelisted {});
+}
+
+#[test]
+fn prune_balance_history() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let oldest = contracts
+        .governance
+        .query_oldest_open_proposal(&mut app)
+        .unwrap();
+    assert_eq!(oldest.id, None);
+
+    app.update_block(next_block);
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TransferWhitelist {
+                title: "Whitelist".into(),
+                description: "Let the distribution contract hold bJMES".into(),
+                metadata: None,
+                add: vec![contracts.distribution.addr().to_string()],
+                remove: vec![],
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+    let start_block = app.block_info().height;
+
+    let oldest = contracts
+        .governance
+        .query_oldest_open_proposal(&mut app)
+        .unwrap();
+    assert_eq!(oldest.id, Some(1));
+    assert_eq!(oldest.start_block, Some(start_block));
+
+    app.update_block(next_block);
+    contracts
+        .bjmes_token
+        .mint(&mut app, &user1, user1.to_string(), Uint128::from(500u128))
+        .unwrap();
+
+    // Test that anyone can prune the balances from before the open proposal
+    let prune_res = contracts.bjmes_token.prune(&mut app, &owner, None).unwrap();
+    assert_eq!(
+        get_attribute(&prune_res, "wasm", "retain_from"),
+        start_block.to_string()
+    );
+    assert_ne!(get_attribute(&prune_res, "wasm", "pruned"), "0");
+
+    contracts
+        .bjmes_token
+        .query_voting_power_at(&mut app, &user1, start_block - 1)
+        .unwrap_err();
+
+    // Test that voting on the open proposal still works
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
+    );
+
+    let oldest = contracts
+        .governance
+        .query_oldest_open_proposal(&mut app)
+        .unwrap();
+    assert_eq!(oldest.id, None);
+}
+
+#[test]
+fn governance_funding_proposal_failing() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Governance Proposal Msg
+    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::Funding {
+        title: "Funding".to_string(),
+        description: "Give me money".to_string(),
+        metadata: None,
+        duration: FUNDING_DURATION,
+        amount: Uint128::from(FUNDING_AMOUNT),
+        vesting: None,
+        asset: None,
+    });
+
+    // Create, vote on and execute the dao proposal
+    DaoMultisigContract::gov_proposal_helper(
+        &mut app,
+        my_dao_addr.clone(),
+        &contracts.governance.addr().clone(),
+        user1.clone(),
+        user2.clone(),
+        to_binary(&proposal_msg),
+        PROPOSAL_REQUIRED_DEPOSIT,
+    )
+    .unwrap();
+
+    let period_info_posting = contracts.governance.query_period_info(&mut app).unwrap();
+    println!("\n\n period_info_posting {:?}", period_info_posting);
+    assert_eq!(period_info_posting.current_period, ProposalPeriod::Posting);
+
+    // Skip period from Posting to VotingBLOKSECNDS
+    app.update_block(|mut block| {
+        block.time = block
+            .time
+            .plus_seconds(period_info_posting.posting_period_length);
+        block.height += period_info_posting.posting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    let period_info_voting = contracts.governance.query_period_info(&mut app).unwrap();
+    println!("\n\n period_info_voting{:#?}", period_info_voting);
+
+    assert_eq!(
+        period_info_voting,
+        PeriodInfoResponse {
+            current_block: 12355,
+            current_period: ProposalPeriod::Voting,
+            current_time_in_cycle: 60,
+            current_posting_start: 1660000000,
+            current_voting_start: 1660000040,
+            current_voting_end: 1660000080,
+            next_posting_start: 1660000080,
+            next_voting_start: 1660000120,
+            posting_period_length: 40,
+            voting_period_length: 40,
+            cycle_length: 80
+        }
+    );
+
+    // User1 votes no on the governance proposal to fail it
+
+    let fund_proposal_vote = contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::No)
+        .unwrap();
+    println!("\n\n fund_proposal_vote {:?}", fund_proposal_vote);
+
+    let proposal_result = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    println!("\n\n proposal_result {:?}", proposal_result);
+
+    // Test that you can't conclude a proposal in the voting period
+    let voting_not_ended_err = contracts
+        .governance
+        .conclude(&mut app, &user1, 1)
+        .unwrap_err();
+    assert_eq!(voting_not_ended_err, ContractError::VotingPeriodNotEnded {});
+
+    // Skip period from Voting to Posting so we can conclude the prBLOoKSECNDS
+    app.update_block(|mut block| {
+        block.time = block
+            .time
+            .plus_seconds(period_info_posting.voting_period_length);
+        block.height += period_info_posting.voting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    let period_info_posting2 = contracts.governance.query_period_info(&mut app).unwrap();
+    println!("\n\n period_info_posting2 {:?}", period_info_posting2);
+
+    let conclude_proposal_result = contracts.governance.conclude(&mut app, &user1, 1).unwrap();
+    println!(
+        "\n\n conclude_proposal_result {:?}",
+        conclude_proposal_result
+    );
+
+    // Test that you can't conclude a proposal (and execute its msgs) a second time
+    let conclude2_proposal_result = contracts
+        .governance
+        .conclude(&mut app, &user1, 1)
+        .unwrap_err();
+    assert_eq!(
+        conclude2_proposal_result,
+        ContractError::ProposalAlreadyConcluded {}
+    );
+    println!(
+        "\n\n conclude2_proposal_result {:?}",
+        conclude2_proposal_result
+    );
+
+    // Skip half the grant duration time to allow us to test if the failing proposal lets us claim funds
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(FUNDING_DURATION / 2);
+        block.height += FUNDING_DURATION / 2 / SECONDS_PER_BLOCK;
+    });
+
+    let claim_funds_err = contracts
+        .distribution
+        .claim(&mut app, &user1, 1)
+        .unwrap_err();
+    assert_eq!(
+        claim_funds_err,
+        distribution::ContractError::GrantNotFound {}
+    );
+
+    assert_eq!(
+        app.wrap()
+            .query_all_balances(Addr::unchecked(my_dao_addr.clone()))
+            .unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        app.wrap()
+            .query_all_balances(contracts.distribution.addr().clone())
+            .unwrap(),
+        coins(DISTRIBUTION_INIT_BALANCE, "uluna")
+    );
+}
+
+#[test]
+fn proposal_metadata_and_unicode_text() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Test that a malformed content hash is rejected
+    let invalid_hash_err = contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Specification".into(),
+                description: "See metadata".into(),
+                metadata: Some(ProposalMetadata {
+                    uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+                        .into(),
+                    content_hash: "not a hash".into(),
+                }),
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap_err();
+    assert_eq!(
+        invalid_hash_err,
+        ContractError::ProposalNotValid {
+            error: "Metadata content hash is not a hex encoded sha256 hash!".into()
+        }
+    );
+
+    // Test that only ipfs:// and https:// links are accepted
+    let invalid_uri_err = contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Specification".into(),
+                description: "See metadata".into(),
+                metadata: Some(ProposalMetadata {
+                    uri: "http://example.com/spec.md".into(),
+                    content_hash: "ab".repeat(32),
+                }),
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap_err();
+    assert_eq!(
+        invalid_uri_err,
+        ContractError::ProposalNotValid {
+            error: "Metadata uri must be an ipfs:// or https:// link!".into()
+        }
+    );
+
+    // Test that control characters are rejected in the title
+    let control_char_err = contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Line\nbreak".into(),
+                description: "Not allowed in titles".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap_err();
+    assert_eq!(
+        control_char_err,
+        ContractError::ProposalNotValid {
+            error: "Title contains control characters!".into()
+        }
+    );
+
+    // Test that invisible format characters, like bidi overrides, are rejected
+    let format_char_err = contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Fund \u{202e}tsil".into(),
+                description: "Reads differently than stored".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap_err();
+    assert_eq!(
+        format_char_err,
+        ContractError::ProposalNotValid {
+            error: "Title contains format characters!".into()
+        }
+    );
+    let format_char_err = contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Zero width".into(),
+                description: "Hidden\u{200b}space".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap_err();
+    assert_eq!(
+        format_char_err,
+        ContractError::ProposalNotValid {
+            error: "Description contains format characters!".into()
+        }
+    );
+
+    // Submit a proposal with unicode text (decomposed "e" + acute accent) and metadata
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "  Cafe\u{301} für alle 🌱 ".into(),
+                description: "Spécification complète:\n- voir le document".into(),
+                metadata: Some(ProposalMetadata {
+                    uri: "https://example.com/spec.md".into(),
+                    content_hash: "AB".repeat(32),
+                }),
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    // Test that the text is stored normalized and the metadata is stored
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert_eq!(proposal.title, "Caf\u{e9} für alle 🌱");
+    assert_eq!(
+        proposal.description,
+        "Spécification complète:\n- voir le document"
+    );
+    assert_eq!(
+        proposal.metadata,
+        Some(ProposalMetadata {
+            uri: "https://example.com/spec.md".into(),
+            content_hash: "ab".repeat(32),
+        })
+    );
+}
+
+#[test]
+fn simulate_proposal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let funding_msg = ProposalMsg::Funding {
+        title: "Funding".to_string(),
+        description: "Give me money".to_string(),
+        metadata: None,
+        duration: FUNDING_DURATION,
+        amount: Uint128::from(FUNDING_AMOUNT),
+        vesting: None,
+        asset: None,
+    };
+
+    // Test that only DAO identities pass the simulation
+    let user_err = contracts
+        .governance
+        .query_simulate_proposal(
+            &mut app,
+            &user1,
+            PROPOSAL_REQUIRED_DEPOSIT,
+            funding_msg.clone(),
+        )
+        .unwrap_err();
+    assert!(user_err.to_string().contains("Unauthorized"));
+
+    // Test that the deposit is checked
+    let deposit_err = contracts
+        .governance
+        .query_simulate_proposal(
+            &mut app,
+            &my_dao_addr,
+            PROPOSAL_REQUIRED_DEPOSIT - 1,
+            funding_msg.clone(),
+        )
+        .unwrap_err();
+    assert!(deposit_err
+        .to_string()
+        .contains("Insufficient token deposit!"));
+
+    // Test that the improvement proposal requires the CoreTech slot
+    let slot_err = contracts
+        .governance
+        .query_simulate_proposal(
+            &mut app,
+            &my_dao_addr,
+            PROPOSAL_REQUIRED_DEPOSIT,
+            ProposalMsg::Improvement {
+                title: "Send funds".into(),
+                description: "BankMsg".into(),
+                metadata: None,
+                msgs: vec![],
+            },
+        )
+        .unwrap_err();
+    assert!(slot_err.to_string().contains("Unauthorized"));
+
+    // Test that a valid funding proposal returns the proposal and its AddGrant msg
+    let simulation = contracts
+        .governance
+        .query_simulate_proposal(
+            &mut app,
+            &my_dao_addr,
+            PROPOSAL_REQUIRED_DEPOSIT,
+            funding_msg,
+        )
+        .unwrap();
+    assert_eq!(simulation.proposal.id, 1);
+    assert_eq!(simulation.proposal.dao, my_dao_addr);
+    assert_eq!(simulation.proposal.prop_type, ProposalType::Funding {});
+    assert_eq!(simulation.proposal.status, ProposalStatus::Posted);
+    assert_eq!(
+        simulation.msgs,
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contracts.distribution.addr().to_string(),
+            msg: to_binary(&AddGrantMsg {
+                add_grant: AddGrant {
+                    dao: my_dao_addr.clone(),
+                    duration: FUNDING_DURATION,
+                    amount: Uint128::from(FUNDING_AMOUNT),
+                    vesting: None,
+                    asset: None,
+                    start: None,
+                },
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    // Test that nothing was saved by the simulation
+    contracts
+        .governance
+        .query_proposal(&mut app, 1)
+        .unwrap_err();
+}
+
+#[test]
+fn voter_participation_stats() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Fund the dao with a second deposit
+    app.send_tokens(
+        contracts.distribution.addr().clone(),
+        my_dao_addr.clone(),
+        &coins(PROPOSAL_REQUIRED_DEPOSIT, "uluna"),
+    )
+    .unwrap();
+
+    // Post two proposals in the same cycle
+    for title in ["First proposal", "Second proposal"] {
+        contracts
+            .governance
+            .propose(
+                &mut app,
+                &my_dao_addr,
+                ProposalMsg::TextProposal {
+                    title: title.into(),
+                    description: "Participation".into(),
+                    metadata: None,
+                },
+                PROPOSAL_REQUIRED_DEPOSIT,
+            )
+            .unwrap();
+    }
+
+    let period_info = contracts.governance.query_period_info(&mut app).unwrap();
+    let cycle = period_info.current_posting_start / period_info.cycle_length;
+
+    // Skip period from Posting to Voting
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.posting_period_length);
+        block.height += period_info.posting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    // User1 votes on the first proposal only, user2 votes on both
+    contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::Yes)
+        .unwrap();
+    contracts
+        .governance
+        .vote(&mut app, &user2, 1, VoteOption::No)
+        .unwrap();
+    contracts
+        .governance
+        .vote(&mut app, &user2, 2, VoteOption::Yes)
+        .unwrap();
+
+    let user1_votes = contracts
+        .governance
+        .query_votes_by_voter(&mut app, &user1, None, None)
+        .unwrap();
+    assert_eq!(
+        user1_votes.votes,
+        vec![VoteRecord {
+            proposal_id: 1,
+            vote: VoteOption::Yes,
+            coins: Uint128::from(USER1_VOTING_COINS),
+            cycle,
+        }]
+    );
+
+    // Test pagination of the votes
+    let user2_votes = contracts
+        .governance
+        .query_votes_by_voter(&mut app, &user2, Some(1), None)
+        .unwrap();
+    assert_eq!(
+        user2_votes.votes,
+        vec![VoteRecord {
+            proposal_id: 2,
+            vote: VoteOption::Yes,
+            coins: Uint128::from(USER2_VOTING_COINS),
+            cycle,
+        }]
+    );
+
+    let user1_stats = contracts
+        .governance
+        .query_voter_stats(&mut app, &user1, None, None)
+        .unwrap();
+    assert_eq!(user1_stats.votes, 1);
+    assert_eq!(user1_stats.participation_rate, Decimal::percent(50));
+    assert_eq!(
+        user1_stats.cycles,
+        vec![VoterCycleStatsResponse {
+            cycle,
+            votes: 1,
+            yes_votes: 1,
+            no_votes: 0,
+            proposals: 2,
+            participation_rate: Decimal::percent(50),
+        }]
+    );
+
+    let user2_stats = contracts
+        .governance
+        .query_voter_stats(&mut app, &user2, None, None)
+        .unwrap();
+    assert_eq!(user2_stats.votes, 2);
+    assert_eq!(user2_stats.yes_votes, 1);
+    assert_eq!(user2_stats.no_votes, 1);
+    assert_eq!(user2_stats.participation_rate, Decimal::one());
+
+    // Test pagination of the cycles, the totals still cover all cycles
+    let user2_stats = contracts
+        .governance
+        .query_voter_stats(&mut app, &user2, Some(cycle), None)
+        .unwrap();
+    assert_eq!(user2_stats.votes, 2);
+    assert_eq!(user2_stats.cycles, vec![]);
+
+    // An address that never voted has a zero participation rate
+    let owner_stats = contracts
+        .governance
+        .query_voter_stats(&mut app, &owner, None, None)
+        .unwrap();
+    assert_eq!(owner_stats.votes, 0);
+    assert_eq!(owner_stats.participation_rate, Decimal::zero());
+}
+
+#[test]
+fn voting_rewards_from_forfeited_deposit() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    // Half of the forfeited deposits go to the voters
+    let contracts = instantiate_contracts_with_voting_reward(
+        &mut app,
+        user1.clone(),
+        user2.clone(),
+        owner.clone(),
+        50,
+    );
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Rejected".into(),
+                description: "Deposit is forfeited".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    let period_info = contracts.governance.query_period_info(&mut app).unwrap();
+    let cycle = period_info.current_posting_start / period_info.cycle_length;
+
+    // Skip period from Posting to Voting
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.posting_period_length);
+        block.height += period_info.posting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    // Both users reject the proposal
+    contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::No)
+        .unwrap();
+    contracts
+        .governance
+        .vote(&mut app, &user2, 1, VoteOption::No)
+        .unwrap();
+
+    // Test that nothing can be claimed while the cycle is still running
+    let claim_err = contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user1, None, None)
+        .unwrap_err();
+    assert_eq!(claim_err, ContractError::NoVotingRewards {});
+
+    // Skip period from Voting to Posting and conclude the rejected proposal
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.voting_period_length);
+        block.height += period_info.voting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    let distribution_balance = app
+        .wrap()
+        .query_balance(contracts.distribution.addr(), "uluna")
+        .unwrap()
+        .amount;
+
+    contracts.governance.conclude(&mut app, &user1, 1).unwrap();
+
+    // Test that the deposit is split between the reward pool and the distribution contract
+    assert_eq!(
+        app.wrap()
+            .query_balance(contracts.distribution.addr(), "uluna")
+            .unwrap()
+            .amount,
+        distribution_balance + Uint128::from(PROPOSAL_REQUIRED_DEPOSIT / 2)
+    );
+    let pool = contracts
+        .governance
+        .query_voting_reward_pool(&mut app, cycle)
+        .unwrap();
+    assert_eq!(pool.amount, Uint128::from(PROPOSAL_REQUIRED_DEPOSIT / 2));
+    assert_eq!(
+        pool.total_vote_coins,
+        Uint128::from(USER1_VOTING_COINS + USER2_VOTING_COINS)
+    );
+
+    // The pool is split by voting weight: 2000 / 5000 and 3000 / 5000 of 500
+    let user2_rewards = contracts
+        .governance
+        .query_voting_rewards(&mut app, &user2)
+        .unwrap();
+    assert_eq!(user2_rewards.claimable_amount, Uint128::from(300u128));
+
+    contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user1, None, None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(user1.clone()).unwrap(),
+        coins(200, "uluna")
+    );
+
+    // Test that rewards can only be claimed once
+    let claim_err = contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user1, None, None)
+        .unwrap_err();
+    assert_eq!(claim_err, ContractError::NoVotingRewards {});
+
+    contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user2, None, None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(user2.clone()).unwrap(),
+        coins(300, "uluna")
+    );
+}
+
+#[test]
+fn voting_rewards_without_votes_and_dust() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    // Half of the forfeited deposits go to the voters
+    let contracts = instantiate_contracts_with_voting_reward(
+        &mut app,
+        user1.clone(),
+        user2.clone(),
+        owner.clone(),
+        50,
+    );
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Fund the dao with two more deposits
+    app.send_tokens(
+        contracts.distribution.addr().clone(),
+        my_dao_addr.clone(),
+        &coins(2 * PROPOSAL_REQUIRED_DEPOSIT, "uluna"),
+    )
+    .unwrap();
+
+    let distribution_balance = |app: &App| {
+        app.wrap()
+            .query_balance(contracts.distribution.addr(), "uluna")
+            .unwrap()
+            .amount
+    };
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Ignored".into(),
+                description: "Nobody votes".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    let period_info = contracts.governance.query_period_info(&mut app).unwrap();
+    let cycle = period_info.current_posting_start / period_info.cycle_length;
+
+    // Skip the whole cycle without votes
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.cycle_length);
+        block.height += period_info.cycle_length / SECONDS_PER_BLOCK;
+    });
+
+    // Test that the whole deposit is forwarded when nobody voted in the cycle
+    let balance_before = distribution_balance(&app);
+    contracts.governance.conclude(&mut app, &user1, 1).unwrap();
+    assert_eq!(
+        distribution_balance(&app),
+        balance_before + Uint128::from(PROPOSAL_REQUIRED_DEPOSIT)
+    );
+    let pool = contracts
+        .governance
+        .query_voting_reward_pool(&mut app, cycle)
+        .unwrap();
+    assert_eq!(pool.amount, Uint128::zero());
+
+    // Post two proposals in the next cycle, user1 votes on both and user2 on one
+    for title in ["First proposal", "Second proposal"] {
+        contracts
+            .governance
+            .propose(
+                &mut app,
+                &my_dao_addr,
+                ProposalMsg::TextProposal {
+                    title: title.into(),
+                    description: "Rejected".into(),
+                    metadata: None,
+                },
+                PROPOSAL_REQUIRED_DEPOSIT,
+            )
+            .unwrap();
+    }
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.posting_period_length);
+        block.height += period_info.posting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    for (voter, id) in [(&user1, 2), (&user1, 3), (&user2, 2)] {
+        contracts
+            .governance
+            .vote(&mut app, voter, id, VoteOption::No)
+            .unwrap();
+    }
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.voting_period_length);
+        block.height += period_info.voting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    contracts.governance.conclude(&mut app, &user1, 2).unwrap();
+    contracts.governance.conclude(&mut app, &user1, 3).unwrap();
+
+    // Test that claims page through the cycles voted in
+    let claim_err = contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user1, Some(cycle + 1), None)
+        .unwrap_err();
+    assert_eq!(claim_err, ContractError::NoVotingRewards {});
+
+    // The pool of 1000 is split 4000 / 7000 and 3000 / 7000, rounding down
+    let res = contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user1, None, Some(1))
+        .unwrap();
+    assert_eq!(
+        get_attribute(&res, "wasm", "last_cycle"),
+        (cycle + 1).to_string()
+    );
+    assert_eq!(
+        app.wrap().query_all_balances(user1.clone()).unwrap(),
+        coins(571, "uluna")
+    );
+
+    // Test that the rounding dust goes to distribution once all voters claimed
+    let balance_before = distribution_balance(&app);
+    contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user2, None, None)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_all_balances(user2.clone()).unwrap(),
+        coins(428, "uluna")
+    );
+    assert_eq!(
+        distribution_balance(&app),
+        balance_before + Uint128::from(1u128)
+    );
+
+    let claim_err = contracts
+        .governance
+        .claim_voting_rewards(&mut app, &user1, None, None)
+        .unwrap_err();
+    assert_eq!(claim_err, ContractError::NoVotingRewards {});
+}
+
+#[test]
+fn guardian_pause_and_unpause_proposal() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Test that only the guardian and core slot DAOs can pause
+    let pause_err = contracts.governance.pause(&mut app, &user1).unwrap_err();
+    assert_eq!(pause_err, ContractError::Unauthorized {});
+    let pause_err = contracts
+        .governance
+        .pause(&mut app, &my_dao_addr)
+        .unwrap_err();
+    assert_eq!(pause_err, ContractError::Unauthorized {});
+
+    contracts.governance.pause(&mut app, &owner).unwrap();
+
+    let paused_until = app.block_info().time.seconds() + MAX_PAUSE_DURATION;
+    let pause_info = contracts.governance.query_pause_info(&mut app).unwrap();
+    assert!(pause_info.paused);
+    assert_eq!(pause_info.paused_until, Some(paused_until));
+    assert_eq!(pause_info.guardian, Some(owner.clone()));
+
+    let pause_err = contracts.governance.pause(&mut app, &owner).unwrap_err();
+    assert_eq!(pause_err, ContractError::AlreadyPaused {});
+
+    // Test that regular proposals can't be posted while paused
+    let propose_err = contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Paused".into(),
+                description: "Can't be posted".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap_err();
+    assert_eq!(propose_err, ContractError::Paused { paused_until });
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::Unpause {
+                title: "Unpause".into(),
+                description: "Resume governance".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    // The unpause proposal can be voted on and concluded while paused
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::Yes,
+        1,
+    );
+
+    let pause_info = contracts.governance.query_pause_info(&mut app).unwrap();
+    assert!(!pause_info.paused);
+    assert_eq!(pause_info.paused_until, None);
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Unpaused".into(),
+                description: "Can be posted again".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    // Test that the guardian can't pause again right after the unpause
+    let unpaused_at = app.block_info().time.seconds();
+    let pause_err = contracts.governance.pause(&mut app, &owner).unwrap_err();
+    assert_eq!(
+        pause_err,
+        ContractError::PauseCooldown {
+            available_at: unpaused_at + MAX_PAUSE_DURATION
+        }
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(MAX_PAUSE_DURATION);
+        block.height += MAX_PAUSE_DURATION / SECONDS_PER_BLOCK;
+    });
+    contracts.governance.pause(&mut app, &owner).unwrap();
+}
+
+#[test]
+fn pause_blocks_voting_and_expires() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    // Register user identity with valid name
+    contracts
+        .identityservice
+        .register_user(&mut app, &user1, "user1_id".to_string())
+        .unwrap();
+
+    // Create the flex-multisig dao
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    contracts
+        .governance
+        .propose(
+            &mut app,
+            &my_dao_addr,
+            ProposalMsg::TextProposal {
+                title: "Posted".into(),
+                description: "Posted before the pause".into(),
+                metadata: None,
+            },
+            PROPOSAL_REQUIRED_DEPOSIT,
+        )
+        .unwrap();
+
+    contracts.governance.pause(&mut app, &owner).unwrap();
+    let paused_until = app.block_info().time.seconds() + MAX_PAUSE_DURATION;
+
+    // Skip period from Posting to Voting
+    let period_info = contracts.governance.query_period_info(&mut app).unwrap();
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.posting_period_length);
+        block.height += period_info.posting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    // Test that votes and conclusions are blocked while paused
+    let vote_err = contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::Yes)
+        .unwrap_err();
+    assert_eq!(vote_err, ContractError::Paused { paused_until });
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(period_info.voting_period_length);
+        block.height += period_info.voting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    let conclude_err = contracts
+        .governance
+        .conclude(&mut app, &user1, 1)
+        .unwrap_err();
+    assert_eq!(conclude_err, ContractError::Paused { paused_until });
+
+    // Skip past the end of the pause, it is lifted without a proposal
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(MAX_PAUSE_DURATION);
+        block.height += MAX_PAUSE_DURATION / SECONDS_PER_BLOCK;
+    });
+
+    let pause_info = contracts.governance.query_pause_info(&mut app).unwrap();
+    assert!(!pause_info.paused);
+
+    contracts.governance.conclude(&mut app, &user1, 1).unwrap();
 }
@@ -1,20 +1,24 @@
 use crate::{
     error::ContractError,
-    msg::{CoreSlot, Feature, RevokeCoreSlot},
+    msg::{CoreSlot, Feature, ProposalMetadata, RevokeCoreSlot},
 };
 use cosmwasm_std::{Addr, CosmosMsg, Decimal, Env, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_normalization::UnicodeNormalization;
 
-/// Proposal validation attributes
+/// Proposal validation attributes, lengths are counted in unicode characters
 const MIN_TITLE_LENGTH: usize = 4;
 const MAX_TITLE_LENGTH: usize = 64;
 const MIN_DESC_LENGTH: usize = 4;
 const MAX_DESC_LENGTH: usize = 1024;
 
-/// Special characters that are allowed in proposal text
-const SAFE_TEXT_CHARS: &str = "!&?#()*+'-./\"";
+/// Proposal metadata validation attributes
+const MAX_METADATA_URI_LENGTH: usize = 256;
+const METADATA_URI_SCHEMES: [&str; 2] = ["ipfs://", "https://"];
+const CONTENT_HASH_LENGTH: usize = 64; // Hex encoded sha256
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
@@ -24,6 +28,54 @@ pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 
+// Votes of an address, keyed by (voter, proposal_id)
+pub const VOTES: Map<(&Addr, u64), VoteRecord> = Map::new("votes");
+
+// Number of proposals posted per cycle
+pub const CYCLE_PROPOSAL_COUNT: Map<u64, u64> = Map::new("cycle_proposal_count");
+
+// Votes cast by an address per cycle, keyed by (voter, cycle)
+pub const VOTER_CYCLE_STATS: Map<(&Addr, u64), VoterCycleStats> = Map::new("voter_cycle_stats");
+
+// Votes cast by an address over all cycles
+pub const VOTER_TOTALS: Map<&Addr, VoterTotals> = Map::new("voter_totals");
+
+// Sum of the vote coins of all votes cast per cycle
+pub const CYCLE_VOTE_COINS: Map<u64, Uint128> = Map::new("cycle_vote_coins");
+
+// Forfeited deposits (uluna) to be shared by the voters of a cycle
+pub const VOTING_REWARD_POOLS: Map<u64, Uint128> = Map::new("voting_reward_pools");
+
+// Voting rewards paid out per cycle, including the rounding dust sent to distribution
+pub const CYCLE_REWARDS_PAID: Map<u64, Uint128> = Map::new("cycle_rewards_paid");
+
+// Vote coins of the voters that claimed their share of a cycle's current reward pool
+pub const CYCLE_REWARD_SETTLEMENTS: Map<u64, RewardSettlement> =
+    Map::new("cycle_reward_settlements");
+
+// Uluna granted per cycle, counting towards the funding budget of that cycle
+pub const CYCLE_FUNDING: Map<u64, Uint128> = Map::new("cycle_funding");
+
+// Cycle and amount booked for a concluded funding proposal, released if adding the grant fails
+pub const FUNDING_BOOKINGS: Map<u64, (u64, Uint128)> = Map::new("funding_bookings");
+
+/// Number of core slot DAOs that need to request a pause
+pub const CORE_SLOT_PAUSE_QUORUM: usize = 2;
+
+// End (in seconds) of the current or last pause, a new pause can start once the maximum pause
+// duration passed since then
+pub const PAUSED_UNTIL: Item<u64> = Item::new("paused_until");
+
+// Pause requests of core slot DAOs, keyed by dao with the time of the request
+pub const PAUSE_REQUESTS: Map<&Addr, u64> = Map::new("pause_requests");
+
+/// Returns the end of the pause if governance is currently paused
+pub fn active_pause(store: &dyn Storage, env: &Env) -> StdResult<Option<u64>> {
+    Ok(PAUSED_UNTIL
+        .may_load(store)?
+        .filter(|paused_until| env.block.time.seconds() < *paused_until))
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct SlotVoteResult {
@@ -38,6 +90,17 @@ pub struct CoreSlots {
     pub creative: Option<SlotVoteResult>,
     pub core_tech: Option<SlotVoteResult>,
 }
+
+impl CoreSlots {
+    /// DAOs currently holding a core slot
+    pub fn daos(&self) -> Vec<Addr> {
+        [&self.brand, &self.creative, &self.core_tech]
+            .into_iter()
+            .flatten()
+            .map(|slot| slot.dao.clone())
+            .collect()
+    }
+}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Config {
@@ -55,7 +118,29 @@ pub struct Config {
     pub posting_period_length: u64,
     // Length in seconds of the posting period, e.g.  606864 for ~ 1 Week (year/52)
     pub voting_period_length: u64,
+    // Percentage of forfeited proposal deposits paid out to the voters of the cycle, e.g. 10
+    pub voting_reward_percentage: u64,
+    // Address that can pause governance in an emergency
+    pub guardian: Option<Addr>,
+    // Length in seconds after which a pause is lifted automatically
+    pub max_pause_duration: u64,
+    // Max total uluna granted by funding proposals concluded in a cycle, unlimited if not set
+    pub funding_budget_per_cycle: Option<Uint128>,
+}
+
+impl Config {
+    /// Number of the governance cycle (posting + voting period) that `time` falls into
+    pub fn cycle(&self, time: u64) -> u64 {
+        time.saturating_sub(self.period_start_epoch)
+            / (self.posting_period_length + self.voting_period_length)
+    }
+
+    /// Time the posting period of `cycle` starts
+    pub fn cycle_start(&self, cycle: u64) -> u64 {
+        self.period_start_epoch + cycle * (self.posting_period_length + self.voting_period_length)
+    }
 }
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Proposal {
@@ -63,6 +148,7 @@ pub struct Proposal {
     pub dao: Addr,
     pub title: String,
     pub description: String,
+    pub metadata: Option<ProposalMetadata>,
     pub prop_type: ProposalType,
     pub coins_yes: Uint128,
     pub coins_no: Uint128,
@@ -75,6 +161,9 @@ pub struct Proposal {
     pub voting_end: u64,
     pub concluded: bool,
     pub msgs: Option<Vec<CosmosMsg>>,
+    // Error of a passed grant proposal whose distribution message failed when concluding
+    #[serde(default)]
+    pub execution_error: Option<String>,
 }
 
 impl Proposal {
@@ -121,45 +210,101 @@ impl Proposal {
         status
     }
 
+    /// Trims the proposal text and converts it to Unicode Normalization Form C, so equal
+    /// looking text is always stored (and counted) the same way
+    pub fn normalize(&mut self) {
+        self.title = self.title.trim().nfc().collect();
+        self.description = self.description.trim().nfc().collect();
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.uri = metadata.uri.trim().to_string();
+            metadata.content_hash = metadata.content_hash.trim().to_lowercase();
+        }
+    }
+
     pub fn validate(&self) -> Result<(), ContractError> {
         // Title validation
-        if self.title.len() < MIN_TITLE_LENGTH {
+        let title_length = self.title.chars().count();
+        if title_length < MIN_TITLE_LENGTH {
             return Err(ContractError::ProposalNotValid {
                 error: "Title too short!".into(),
             });
         }
-        if self.title.len() > MAX_TITLE_LENGTH {
+        if title_length > MAX_TITLE_LENGTH {
             return Err(ContractError::ProposalNotValid {
                 error: "Title too long!".into(),
             });
         }
-        if !self.title.chars().all(|c| {
-            c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || SAFE_TEXT_CHARS.contains(c)
-        }) {
+        if self.title.chars().any(char::is_control) {
+            return Err(ContractError::ProposalNotValid {
+                error: "Title contains control characters!".into(),
+            });
+        }
+        if self.title.chars().any(is_format) {
             return Err(ContractError::ProposalNotValid {
-                error: "Title is not in alphanumeric format!".into(),
+                error: "Title contains format characters!".into(),
             });
         }
 
         // Description validation
-        if self.description.len() < MIN_DESC_LENGTH {
+        let description_length = self.description.chars().count();
+        if description_length < MIN_DESC_LENGTH {
             return Err(ContractError::ProposalNotValid {
                 error: "Description too short!".into(),
             });
         }
-        if self.description.len() > MAX_DESC_LENGTH {
+        if description_length > MAX_DESC_LENGTH {
             return Err(ContractError::ProposalNotValid {
                 error: "Description too long!".into(),
             });
         }
-        if !self.description.chars().all(|c| {
-            c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || SAFE_TEXT_CHARS.contains(c)
-        }) {
+        // Line breaks and tabs are allowed to format the description
+        if self
+            .description
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+        {
             return Err(ContractError::ProposalNotValid {
-                error: "Description is not in alphanumeric format".into(),
+                error: "Description contains control characters!".into(),
+            });
+        }
+        if self.description.chars().any(is_format) {
+            return Err(ContractError::ProposalNotValid {
+                error: "Description contains format characters!".into(),
             });
         }
 
+        // Metadata validation
+        if let Some(metadata) = &self.metadata {
+            if !METADATA_URI_SCHEMES
+                .iter()
+                .any(|scheme| metadata.uri.starts_with(scheme) && metadata.uri.len() > scheme.len())
+            {
+                return Err(ContractError::ProposalNotValid {
+                    error: "Metadata uri must be an ipfs:// or https:// link!".into(),
+                });
+            }
+            if metadata.uri.len() > MAX_METADATA_URI_LENGTH {
+                return Err(ContractError::ProposalNotValid {
+                    error: "Metadata uri too long!".into(),
+                });
+            }
+            if !metadata.uri.chars().all(|c| c.is_ascii_graphic()) {
+                return Err(ContractError::ProposalNotValid {
+                    error: "Metadata uri contains invalid characters!".into(),
+                });
+            }
+            if metadata.content_hash.len() != CONTENT_HASH_LENGTH
+                || !metadata
+                    .content_hash
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+            {
+                return Err(ContractError::ProposalNotValid {
+                    error: "Metadata content hash is not a hex encoded sha256 hash!".into(),
+                });
+            }
+        }
+
         Ok(())
     }
 }
@@ -183,6 +328,12 @@ pub enum ProposalType {
     Improvement {},
     CoreSlot(CoreSlot),
     RevokeCoreSlot(RevokeCoreSlot),
+    RevokeGrant { grant_id: u64 },
+    PauseGrant { grant_id: u64 },
+    ResumeGrant { grant_id: u64 },
+    ApproveMilestone { grant_id: u64, milestone: u32 },
+    TransferWhitelist {},
+    Unpause {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -191,3 +342,63 @@ pub enum VoteOption {
     Yes,
     No,
 }
+
+/// Unicode format characters (general category Cf), like zero-width spaces and bidi overrides.
+/// They are invisible, so text containing them can display differently from what is stored
+fn is_format(c: char) -> bool {
+    get_general_category(c) == GeneralCategory::Format
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VoteRecord {
+    pub proposal_id: u64,
+    pub vote: VoteOption,
+    pub coins: Uint128,
+    pub cycle: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VoterTotals {
+    pub yes_votes: u64,
+    pub no_votes: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VoterCycleStats {
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    // Sum of the vote coins, used as weight for the voting rewards
+    pub vote_coins: Uint128,
+    pub rewards_claimed: Uint128,
+    // Reward pool the voter last claimed from, nothing is left to claim until it grows
+    #[serde(default)]
+    pub settled_pool: Uint128,
+}
+
+impl VoterCycleStats {
+    /// Share of the cycle's reward pool that has not been claimed yet, never more than what is
+    /// left of the pool
+    pub fn claimable_rewards(
+        &self,
+        pool: Uint128,
+        total_vote_coins: Uint128,
+        paid: Uint128,
+    ) -> Uint128 {
+        if total_vote_coins.is_zero() || self.settled_pool == pool {
+            return Uint128::zero();
+        }
+        pool.multiply_ratio(self.vote_coins, total_vote_coins)
+            .saturating_sub(self.rewards_claimed)
+            .min(pool.saturating_sub(paid))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RewardSettlement {
+    pub pool: Uint128,
+    pub vote_coins: Uint128,
+}
@@ -75,6 +75,10 @@ async function instantiateContracts(client, user, options = {}) {
         symbol: "bjmes",
         decimals: 10,
         initial_balances: [],
+        bonding: null, // { denom, unbonding_period: { time: seconds } } lets holders bond native JMES
+        max_lock_duration: null, // longest lock in blocks for extra voting power, locking disabled if null
+        admin: process.env.OWNER, // manages hooks and minters, hand over to governance with update_admin
+        emission: null, // [{ start: seconds, recipients: [{ address, amount }] }] minted by emit, disabled if null
       },
     },
     {
@@ -89,6 +93,10 @@ async function instantiateContracts(client, user, options = {}) {
         period_start_epoch: Math.floor(Date.now() / 1000), //1660000000,
         posting_period_length: 70,
         voting_period_length: 20,
+        voting_reward_percentage: 10, // share of forfeited deposits paid to the cycle's voters
+        guardian: null, // address that can pause governance in an emergency
+        max_pause_duration: 1209600, // pauses are lifted automatically after ~2 weeks
+        funding_budget_per_cycle: null, // max uluna granted per cycle, unlimited if null
       },
     },
     {
@@ -159,6 +167,27 @@ async function instantiateContracts(client, user, options = {}) {
 
   console.log("result :>> ", result);
 
+  // Governance manages the bJMES transfer whitelist and prunes its balance history
+  const updateGovernanceMsg = new MsgExecuteContract(
+    user.address,
+    contractAddrs.bjmes_token,
+    {
+      update_governance: {
+        governance: contractAddrs.governance,
+      },
+    }
+  );
+
+  console.log("updateGovernanceMsg :>> ", updateGovernanceMsg);
+
+  const updateGovernanceResult = await executeMsg(
+    client,
+    updateGovernanceMsg,
+    user.wallet
+  );
+
+  console.log("updateGovernanceResult :>> ", updateGovernanceResult);
+
   console.log("contractAddrs :>> ", readContractAddrs());
 
   return readContractAddrs();